@@ -0,0 +1,45 @@
+use crate::{polkadot, PolkadotRuntimeApi};
+use std::error::Error;
+use subxt::{sp_core::H256, sp_runtime::AccountId32};
+
+pub type AccountData = polkadot::runtime_types::pallet_balances::AccountData<u128>;
+pub type AccountInfo = polkadot::runtime_types::frame_system::AccountInfo<u32, AccountData>;
+
+/// Returns `at`, or the finalized head so that reads over many blocks see a consistent state.
+pub async fn pin_block(api: &PolkadotRuntimeApi, at: Option<H256>) -> Result<H256, Box<dyn Error>> {
+    match at {
+        Some(block_hash) => Ok(block_hash),
+        None => Ok(api.client.rpc().finalized_head().await?),
+    }
+}
+
+/// Returns the number of the block with `block_hash`.
+pub async fn block_number(api: &PolkadotRuntimeApi, block_hash: H256) -> Result<u32, Box<dyn Error>> {
+    let header = api.client.rpc().header(Some(block_hash)).await?;
+    Ok(header.ok_or("Block not found")?.number)
+}
+
+/// Fetches every `system.account` entry at `block_hash`.
+pub async fn get_all_accounts(
+    api: &PolkadotRuntimeApi,
+    block_hash: H256,
+) -> Result<Vec<(AccountId32, AccountInfo)>, Box<dyn Error>> {
+    let mut iter = api.storage().system().account_iter(Some(block_hash)).await?;
+    let mut accounts = Vec::new();
+    while let Some((key, info)) = iter.next().await? {
+        // the key is hashed with `Blake2_128Concat` so it ends with the account id
+        let account: [u8; 32] = key.0[key.0.len() - 32..].try_into()?;
+        accounts.push((AccountId32::new(account), info));
+    }
+    Ok(accounts)
+}
+
+/// Derives the account of a pallet from its `PalletId` constant, e.g. the treasury pot.
+///
+/// Source: https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/primitives/runtime/src/traits.rs
+pub fn pallet_account(pallet_id: [u8; 8]) -> AccountId32 {
+    let mut account = [0u8; 32];
+    account[..4].copy_from_slice(b"modl");
+    account[4..12].copy_from_slice(&pallet_id);
+    AccountId32::new(account)
+}
@@ -0,0 +1,81 @@
+use crate::PolkadotRuntimeApi;
+use std::{error::Error, fmt, str::FromStr};
+use subxt::{
+    sp_core::crypto::{Ss58AddressFormat, Ss58AddressFormatRegistry, Ss58Codec},
+    sp_runtime::AccountId32,
+};
+
+pub const POLKADOT_PREFIX: u16 = Ss58AddressFormatRegistry::PolkadotAccount as u16;
+pub const KUSAMA_PREFIX: u16 = Ss58AddressFormatRegistry::KusamaAccount as u16;
+/// Generic substrate prefix, this is what `AccountId32` uses by default.
+pub const GENERIC_PREFIX: u16 = Ss58AddressFormatRegistry::SubstrateAccount as u16;
+
+/// Reads the `SS58Prefix` constant of the connected chain.
+pub fn chain_prefix(api: &PolkadotRuntimeApi) -> Result<u16, Box<dyn Error>> {
+    Ok(api.constants().system().ss58_prefix()?)
+}
+
+/// An account with the network prefix used to format it as SS58.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Address {
+    pub account: AccountId32,
+    pub prefix: u16,
+}
+
+impl Address {
+    pub fn new(account: AccountId32, prefix: u16) -> Self {
+        Self { account, prefix }
+    }
+
+    /// Formats the `account` for the connected chain.
+    pub fn for_chain(api: &PolkadotRuntimeApi, account: AccountId32) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new(account, chain_prefix(api)?))
+    }
+
+    /// Parses an SS58 `address`, checking that it is for the network with `prefix` or generic.
+    pub fn parse_for(address: &str, prefix: u16) -> Result<Self, Box<dyn Error>> {
+        let address: Self = address.parse()?;
+        if address.prefix != prefix && address.prefix != GENERIC_PREFIX {
+            return Err(format!("Address is for network {} instead of {prefix}", address.prefix).into());
+        }
+        Ok(address)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            &self
+                .account
+                .to_ss58check_with_version(Ss58AddressFormat::custom(self.prefix)),
+        )
+    }
+}
+
+impl FromStr for Address {
+    type Err = Box<dyn Error>;
+
+    /// Parses an SS58 address for any network, validating the checksum.
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        let (account, format) = AccountId32::from_ss58check_with_version(address.trim())
+            .map_err(|err| format!("Invalid address `{address}`: {err}"))?;
+        Ok(Self::new(account, format.into()))
+    }
+}
+
+impl From<Address> for AccountId32 {
+    fn from(address: Address) -> Self {
+        address.account
+    }
+}
+
+/// Formats the `account` as SS58 with the given network `prefix`.
+pub fn encode(account: &AccountId32, prefix: u16) -> String {
+    Address::new(account.clone(), prefix).to_string()
+}
+
+/// Decodes an SS58 address for any network, returning the account and its network prefix.
+pub fn decode(address: &str) -> Result<(AccountId32, u16), Box<dyn Error>> {
+    let Address { account, prefix } = address.parse()?;
+    Ok((account, prefix))
+}
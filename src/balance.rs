@@ -0,0 +1,251 @@
+use crate::{polkadot, PolkadotRuntimeApi};
+use serde::Serialize;
+use serde_json::Value;
+use std::{cmp::Ordering, error::Error, fmt};
+use subxt::sp_runtime::AccountId32;
+
+/// Decimals and symbol of the native token, e.g. 10 and `DOT` on Polkadot.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Token {
+    pub decimals: u8,
+    pub symbol: String,
+}
+
+impl Token {
+    pub fn new(decimals: u8, symbol: impl Into<String>) -> Self {
+        Self {
+            decimals,
+            symbol: symbol.into(),
+        }
+    }
+
+    /// Reads `tokenDecimals` and `tokenSymbol` from `system_properties`.
+    ///
+    /// Chains with multiple tokens report arrays, the first entry is the native token.
+    pub async fn from_chain(api: &PolkadotRuntimeApi) -> Result<Self, Box<dyn Error>> {
+        let properties = api.client.rpc().system_properties().await?;
+        let first = |value: &Value| match value {
+            Value::Array(values) => values.first().cloned(),
+            value => Some(value.clone()),
+        };
+        let decimals = properties
+            .get("tokenDecimals")
+            .and_then(first)
+            .and_then(|decimals| decimals.as_u64())
+            .ok_or("Missing `tokenDecimals` property")?;
+        let symbol = properties
+            .get("tokenSymbol")
+            .and_then(first)
+            .and_then(|symbol| symbol.as_str().map(ToString::to_string))
+            .unwrap_or_default();
+        Ok(Self::new(decimals.try_into()?, symbol))
+    }
+
+    /// A balance of `plancks` in this token.
+    pub fn balance(&self, plancks: u128) -> Balance {
+        Balance {
+            plancks,
+            token: self.clone(),
+        }
+    }
+
+    /// Parses a human readable amount like `"2.5 DOT"` or `"2.5"`, without float rounding.
+    ///
+    /// Amounts with more decimals than the token are rejected instead of truncated.
+    pub fn parse(&self, amount: &str) -> Result<Balance, String> {
+        let amount = amount.trim();
+        let number = match amount.strip_suffix(self.symbol.as_str()) {
+            Some(number) if !self.symbol.is_empty() => number.trim_end(),
+            _ => amount,
+        };
+        let number = number.replace('_', "");
+        let invalid = || format!("Invalid amount `{amount}`");
+
+        let (whole, fraction) = number.split_once('.').unwrap_or((&number, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+        if fraction.len() > self.decimals as usize {
+            return Err(format!("Amount `{amount}` has more than {} decimals", self.decimals));
+        }
+        let parse = |digits: &str| -> Result<u128, String> {
+            if digits.is_empty() {
+                Ok(0)
+            } else if digits.chars().all(|c| c.is_ascii_digit()) {
+                digits.parse().map_err(|_| invalid())
+            } else {
+                Err(invalid())
+            }
+        };
+        let fraction_decimals = (self.decimals as usize - fraction.len()) as u32;
+        let (whole, fraction) = (parse(whole)?, parse(fraction)?);
+        10u128
+            .checked_pow(self.decimals as u32)
+            .and_then(|unit| whole.checked_mul(unit))
+            .zip(10u128.checked_pow(fraction_decimals))
+            .and_then(|(whole, fraction_unit)| whole.checked_add(fraction * fraction_unit))
+            .map(|plancks| self.balance(plancks))
+            .ok_or_else(|| format!("Amount `{amount}` overflows"))
+    }
+}
+
+/// An amount of the native token in plancks, which formats with the token decimals and symbol.
+///
+/// Formats with 4 decimals by default, e.g. `1.0000 DOT`, the precision can be changed with
+/// `{:.2}`. Digits beyond the precision are truncated, never rounded up.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Balance {
+    pub plancks: u128,
+    pub token: Token,
+}
+
+impl PartialOrd for Balance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.token == other.token).then(|| self.plancks.cmp(&other.plancks))
+    }
+}
+
+impl From<Balance> for u128 {
+    fn from(balance: Balance) -> Self {
+        balance.plancks
+    }
+}
+
+impl fmt::Display for Balance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = self.token.decimals as usize;
+        let precision = f.precision().unwrap_or(4).min(decimals);
+        let digits = format!("{:0>width$}", self.plancks, width = decimals + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - decimals);
+        write!(f, "{whole}")?;
+        if precision > 0 {
+            write!(f, ".{}", &fraction[..precision])?;
+        }
+        if !self.token.symbol.is_empty() {
+            write!(f, " {}", self.token.symbol)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the `free` balance of the `account`.
+pub async fn get_balance(api: PolkadotRuntimeApi, account: AccountId32) -> Result<Balance, Box<dyn Error>> {
+    let token = Token::from_chain(&api).await?;
+    let free = api.storage().system().account(&account, None).await?.data.free;
+    Ok(token.balance(free))
+}
+
+type LockReasons = polkadot::runtime_types::pallet_balances::Reasons;
+
+/// Which operations a [`Lock`] applies to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Reasons {
+    /// Only paying transaction fees.
+    Fee,
+    /// Everything except paying transaction fees.
+    Misc,
+    All,
+}
+
+/// An amount locked by a pallet, entries of `balances.Locks`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lock {
+    /// The 8 byte lock id without padding, e.g. `staking` or `democrac`.
+    pub id: String,
+    pub amount: Balance,
+    pub reasons: Reasons,
+}
+
+impl Lock {
+    /// Name of the pallet which owns the lock, if it is a known id.
+    pub fn reason(&self) -> Option<&'static str> {
+        Some(match self.id.as_str() {
+            "staking" => "Staking",
+            "vesting" => "Vesting",
+            "democrac" => "Democracy",
+            "phrelect" => "Elections",
+            "pyconvot" => "Conviction voting",
+            _ => return None,
+        })
+    }
+}
+
+/// An amount reserved with an id, entries of `balances.Reserves`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reserve {
+    pub id: String,
+    pub amount: Balance,
+}
+
+/// Where the balance of an account is, see [`get_balance_breakdown`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceBreakdown {
+    pub free: Balance,
+    pub reserved: Balance,
+    /// `free` minus the largest lock, this can be transferred or used for fees.
+    pub transferable: Balance,
+    /// Locks overlap, so the locked amount is the largest lock rather than the sum.
+    pub locks: Vec<Lock>,
+    pub named_reserves: Vec<Reserve>,
+    /// Reserved without an id, e.g. deposits for proposals, identities and multisigs.
+    pub unnamed_reserved: Balance,
+}
+
+impl BalanceBreakdown {
+    /// The largest lock.
+    pub fn locked(&self) -> Balance {
+        let locked = self
+            .locks
+            .iter()
+            .map(|lock| lock.amount.plancks)
+            .max()
+            .unwrap_or_default();
+        self.free.token.balance(locked)
+    }
+}
+
+/// Lock and reserve ids are 8 bytes, padded with spaces.
+fn decode_id(id: &[u8; 8]) -> String {
+    String::from_utf8_lossy(id).trim_end().to_string()
+}
+
+/// Combines `system.account`, `balances.Locks` and `balances.Reserves` to show why funds of the
+/// `account` are not transferable.
+pub async fn get_balance_breakdown(
+    api: PolkadotRuntimeApi,
+    account: AccountId32,
+) -> Result<BalanceBreakdown, Box<dyn Error>> {
+    let token = Token::from_chain(&api).await?;
+    let data = api.storage().system().account(&account, None).await?.data;
+    let locks = api.storage().balances().locks(&account, None).await?.0;
+    let reserves = api.storage().balances().reserves(&account, None).await?.0;
+
+    let named_reserves: Vec<Reserve> = reserves
+        .into_iter()
+        .map(|reserve| Reserve {
+            id: decode_id(&reserve.id),
+            amount: token.balance(reserve.amount),
+        })
+        .collect();
+    let named_reserved: u128 = named_reserves.iter().map(|reserve| reserve.amount.plancks).sum();
+
+    Ok(BalanceBreakdown {
+        free: token.balance(data.free),
+        reserved: token.balance(data.reserved),
+        transferable: token.balance(data.free.saturating_sub(data.misc_frozen.max(data.fee_frozen))),
+        locks: locks
+            .into_iter()
+            .map(|lock| Lock {
+                id: decode_id(&lock.id),
+                amount: token.balance(lock.amount),
+                reasons: match lock.reasons {
+                    LockReasons::Fee => Reasons::Fee,
+                    LockReasons::Misc => Reasons::Misc,
+                    LockReasons::All => Reasons::All,
+                },
+            })
+            .collect(),
+        named_reserves,
+        unnamed_reserved: token.balance(data.reserved.saturating_sub(named_reserved)),
+    })
+}
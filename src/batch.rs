@@ -0,0 +1,149 @@
+use crate::{dry_run::describe_dispatch_error, polkadot, tx, EncodedCall, PolkadotRuntimeApi, PolkadotSigner};
+use std::{error::Error, fmt};
+use subxt::{
+    codec::Decode,
+    sp_runtime::{AccountId32, DispatchError, MultiAddress},
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+type UtilityCall = polkadot::runtime_types::pallet_utility::pallet::Call;
+type BatchInterruptedEvent = polkadot::utility::events::BatchInterrupted;
+
+/// The `utility` call used to dispatch a batch.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchMode {
+    /// `batch` stops at the first failing call, keeping the effects of earlier calls.
+    Batch,
+    /// `batch_all` reverts every call if any of them fails.
+    BatchAll,
+    /// `force_batch` continues after failing calls.
+    ///
+    /// Not in the embedded metadata, this is only available if the connected runtime supports it.
+    ForceBatch,
+}
+
+/// Why a call in a batch did not succeed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchItemError {
+    /// The call was dispatched and failed.
+    Failed(String),
+    /// An earlier call failed so this call was never dispatched.
+    NotExecuted,
+    /// The whole extrinsic failed, e.g. a `batch_all` call failed and everything was reverted.
+    Reverted(String),
+}
+
+impl fmt::Display for BatchItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(err) => write!(f, "Call failed: {err}"),
+            Self::NotExecuted => write!(f, "Call was not executed"),
+            Self::Reverted(err) => write!(f, "Batch was reverted: {err}"),
+        }
+    }
+}
+
+impl Error for BatchItemError {}
+
+/// Submits the `calls` in a single `utility` batch, returning the result of each call in the same
+/// order as `calls`.
+///
+/// The results are read from the `ItemCompleted`, `ItemFailed`, `BatchInterrupted` and
+/// `BatchCompleted` events so `calls` cannot contain batches themselves.
+pub async fn submit_batch(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    mode: BatchMode,
+    calls: Vec<EncodedCall>,
+) -> Result<Vec<Result<(), BatchItemError>>, Box<dyn Error>> {
+    if calls.iter().any(|call| {
+        matches!(
+            call,
+            EncodedCall::Utility(UtilityCall::batch { .. } | UtilityCall::batch_all { .. })
+        )
+    }) {
+        return Err("Nested batches are not supported".into());
+    }
+
+    let len = calls.len();
+    let extrinsic = match mode {
+        BatchMode::Batch => {
+            tx::create_signed(
+                api.clone(),
+                &EncodedCall::Utility(UtilityCall::batch { calls }),
+                signer,
+                None,
+            )
+            .await?
+        }
+        BatchMode::BatchAll => {
+            tx::create_signed(
+                api.clone(),
+                &EncodedCall::Utility(UtilityCall::batch_all { calls }),
+                signer,
+                None,
+            )
+            .await?
+        }
+        BatchMode::ForceBatch => {
+            let call = tx::encode_dynamic_call(&api, "Utility", "force_batch", &calls)?;
+            tx::create_signed(api.clone(), &call, signer, None).await?
+        }
+    };
+
+    let events = match tx::submit(&api, extrinsic).await?.wait_for_finalized_success().await {
+        Ok(events) => events,
+        Err(subxt::Error::Module(err)) => {
+            return Ok(vec![
+                Err(BatchItemError::Reverted(format!(
+                    "{}::{}",
+                    err.pallet, err.error
+                )));
+                len
+            ])
+        }
+        Err(subxt::Error::Runtime(err)) => return Ok(vec![Err(BatchItemError::Reverted(format!("{:?}", err.0))); len]),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut results = Vec::with_capacity(len);
+    for event in events.iter_raw() {
+        let event = event?;
+        if event.pallet != "Utility" {
+            continue;
+        }
+        match event.variant.as_str() {
+            "ItemCompleted" => results.push(Ok(())),
+            "ItemFailed" => {
+                // not in the embedded metadata, so decode the error with the `sp_runtime` type
+                let err = DispatchError::decode(&mut &event.data[..])
+                    .map(|err| describe_dispatch_error(&api, &err))
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                results.push(Err(BatchItemError::Failed(err)));
+            }
+            "BatchInterrupted" => {
+                let event = BatchInterruptedEvent::decode(&mut &event.data[..])?;
+                results.resize(event.index as usize, Ok(()));
+                results.push(Err(BatchItemError::Failed(describe_dispatch_error(&api, &event.error))));
+            }
+            // older runtimes do not emit `ItemCompleted`
+            "BatchCompleted" => results.resize(len, Ok(())),
+            _ => (),
+        }
+    }
+    results.resize(len, Err(BatchItemError::NotExecuted));
+    Ok(results)
+}
+
+/// Transfers to each recipient in a `utility.batch`, see [`submit_batch`].
+pub async fn batch_transfer(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    recipients: Vec<(MultiAddress<AccountId32, ()>, u128)>,
+) -> Result<Vec<Result<(), BatchItemError>>, Box<dyn Error>> {
+    let calls = recipients
+        .into_iter()
+        .map(|(dest, value)| EncodedCall::Balances(BalancesCall::transfer { dest, value }))
+        .collect();
+    submit_batch(api, signer, BatchMode::Batch, calls).await
+}
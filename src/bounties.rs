@@ -0,0 +1,198 @@
+use crate::{
+    accounts,
+    council::{self, Motion},
+    polkadot, tx, EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
+};
+use std::error::Error;
+use subxt::{sp_core::H256, sp_runtime::AccountId32};
+
+type BountiesCall = polkadot::runtime_types::pallet_bounties::pallet::Call;
+type RuntimeBountyStatus = polkadot::runtime_types::pallet_bounties::BountyStatus<AccountId32, u32>;
+type BountyProposedEvent = polkadot::bounties::events::BountyProposed;
+type BountyClaimedEvent = polkadot::bounties::events::BountyClaimed;
+
+/// Where a bounty is in its lifecycle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BountyStatus {
+    /// Waiting for council approval.
+    Proposed,
+    /// Approved, waiting to be funded at the next treasury spend.
+    Approved,
+    /// Funded, waiting for a curator.
+    Funded,
+    /// Waiting for the `curator` to accept.
+    CuratorProposed { curator: AccountId32 },
+    /// The `curator` works on the bounty and has to update it before `update_due`.
+    Active { curator: AccountId32, update_due: u32 },
+    /// Awarded to the `beneficiary`, which can claim it from block `unlock_at`.
+    PendingPayout {
+        curator: AccountId32,
+        beneficiary: AccountId32,
+        unlock_at: u32,
+    },
+}
+
+impl From<RuntimeBountyStatus> for BountyStatus {
+    fn from(status: RuntimeBountyStatus) -> Self {
+        match status {
+            RuntimeBountyStatus::Proposed => Self::Proposed,
+            RuntimeBountyStatus::Approved => Self::Approved,
+            RuntimeBountyStatus::Funded => Self::Funded,
+            RuntimeBountyStatus::CuratorProposed { curator } => Self::CuratorProposed { curator },
+            RuntimeBountyStatus::Active { curator, update_due } => Self::Active { curator, update_due },
+            RuntimeBountyStatus::PendingPayout {
+                curator,
+                beneficiary,
+                unlock_at,
+            } => Self::PendingPayout {
+                curator,
+                beneficiary,
+                unlock_at,
+            },
+        }
+    }
+}
+
+/// An entry of `bounties.Bounties` with its description.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bounty {
+    pub index: u32,
+    pub proposer: AccountId32,
+    pub value: u128,
+    /// Paid to the curator from the value.
+    pub fee: u128,
+    pub curator_deposit: u128,
+    /// Deposit of the proposer.
+    pub bond: u128,
+    pub status: BountyStatus,
+    /// From `bounties.BountyDescriptions`, lossy if it is not UTF-8.
+    pub description: String,
+}
+
+/// Reads all bounties at `at` (or the finalized head), ordered by index.
+///
+/// Polkadot 9180 does not have the `child_bounties` pallet yet, so there are no child bounties.
+///
+/// Source: https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/frame/bounties/src/lib.rs
+pub async fn get_bounties(api: PolkadotRuntimeApi, at: Option<H256>) -> Result<Vec<Bounty>, Box<dyn Error>> {
+    let block_hash = accounts::pin_block(&api, at).await?;
+    let mut bounties = Vec::new();
+    let mut iter = api.storage().bounties().bounties_iter(Some(block_hash)).await?;
+    while let Some((key, bounty)) = iter.next().await? {
+        // the key is hashed with `Twox64Concat` so it ends with the index
+        let index = u32::from_le_bytes(key.0[key.0.len() - 4..].try_into()?);
+        let description = api
+            .storage()
+            .bounties()
+            .bounty_descriptions(&index, Some(block_hash))
+            .await?
+            .map(|description| String::from_utf8_lossy(&description.0).to_string())
+            .unwrap_or_default();
+        bounties.push(Bounty {
+            index,
+            proposer: bounty.proposer,
+            value: bounty.value,
+            fee: bounty.fee,
+            curator_deposit: bounty.curator_deposit,
+            bond: bounty.bond,
+            status: bounty.status.into(),
+            description,
+        });
+    }
+    bounties.sort_by_key(|bounty| bounty.index);
+    Ok(bounties)
+}
+
+/// Proposes a bounty of `value`, reserving the deposit from [`crate::deposits::bounty_deposit`].
+///
+/// Returns the index of the new bounty.
+pub async fn propose_bounty(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    value: u128,
+    description: &str,
+) -> Result<u32, Box<dyn Error>> {
+    let call = EncodedCall::Bounties(BountiesCall::propose_bounty {
+        value,
+        description: description.as_bytes().to_vec(),
+    });
+    let events = tx::sign_and_submit(api, call, signer).await?;
+    Ok(events
+        .find_first::<BountyProposedEvent>()?
+        .ok_or("Bounty was not proposed")?
+        .index)
+}
+
+/// Proposes a council motion to approve the bounty, see [`council::vote_motion`] and
+/// [`council::close_motion`].
+///
+/// Approving needs the `ApproveOrigin`, which is 3/5 of the council on Polkadot.
+pub async fn approve_bounty(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    bounty_id: u32,
+    threshold: u32,
+) -> Result<Motion, Box<dyn Error>> {
+    let call = EncodedCall::Bounties(BountiesCall::approve_bounty { bounty_id });
+    council::propose_motion(api, signer, call, threshold).await
+}
+
+/// Proposes a council motion to propose the `curator` of a funded bounty for a `fee`.
+pub async fn propose_curator(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    bounty_id: u32,
+    curator: AccountId32,
+    fee: u128,
+    threshold: u32,
+) -> Result<Motion, Box<dyn Error>> {
+    let call = EncodedCall::Bounties(BountiesCall::propose_curator {
+        bounty_id,
+        curator: curator.into(),
+        fee,
+    });
+    council::propose_motion(api, signer, call, threshold).await
+}
+
+/// Accepts the bounty as the proposed curator, reserving the curator deposit.
+pub async fn accept_curator(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    bounty_id: u32,
+) -> Result<(), Box<dyn Error>> {
+    let call = EncodedCall::Bounties(BountiesCall::accept_curator { bounty_id });
+    tx::sign_and_submit(api, call, signer).await?;
+    Ok(())
+}
+
+/// Awards the bounty to the `beneficiary` as its curator, it can be claimed after
+/// `BountyDepositPayoutDelay`.
+pub async fn award_bounty(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    bounty_id: u32,
+    beneficiary: AccountId32,
+) -> Result<(), Box<dyn Error>> {
+    let call = EncodedCall::Bounties(BountiesCall::award_bounty {
+        bounty_id,
+        beneficiary: beneficiary.into(),
+    });
+    tx::sign_and_submit(api, call, signer).await?;
+    Ok(())
+}
+
+/// Claims an awarded bounty for its beneficiary, anyone can sign this.
+///
+/// Returns the payout to the beneficiary.
+pub async fn claim_bounty(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    bounty_id: u32,
+) -> Result<u128, Box<dyn Error>> {
+    let call = EncodedCall::Bounties(BountiesCall::claim_bounty { bounty_id });
+    let events = tx::sign_and_submit(api, call, signer).await?;
+    Ok(events
+        .find_first::<BountyClaimedEvent>()?
+        .ok_or("Bounty was not claimed")?
+        .payout)
+}
@@ -0,0 +1,201 @@
+use crate::{
+    fees,
+    multisig::{self, MultisigAccount},
+    polkadot, EncodedCall, PolkadotRuntimeApi,
+};
+use std::error::Error;
+use subxt::{codec::Encode, sp_runtime::AccountId32, WrapperKeepOpaque};
+
+pub type ProxyType = polkadot::runtime_types::polkadot_runtime::ProxyType;
+
+type MultisigCall = polkadot::runtime_types::pallet_multisig::pallet::Call;
+type ProxyCall = polkadot::runtime_types::pallet_proxy::pallet::Call;
+type UtilityCall = polkadot::runtime_types::pallet_utility::pallet::Call;
+
+/// How a call was wrapped, from the innermost call outwards.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LayerKind {
+    /// The call passed to [`CallComposer::new`].
+    Call,
+    /// `utility.batch_all` of this many calls.
+    BatchAll { len: usize },
+    /// `proxy.proxy` dispatching as `real`.
+    Proxy { real: AccountId32 },
+    /// `multisig.as_multi` submitted by `signatory` on behalf of the `multisig` account, or
+    /// `as_multi_threshold_1` with a `max_weight` of 0 for a threshold of 1.
+    AsMulti {
+        multisig: AccountId32,
+        signatory: AccountId32,
+        max_weight: u64,
+    },
+}
+
+/// A call of a [`ComposedCall`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Layer {
+    pub kind: LayerKind,
+    /// The hash a multisig approves if this call is its operation, see [`multisig::call_hash`].
+    pub call_hash: [u8; 32],
+    /// The dispatch weight of this call.
+    pub weight: u64,
+}
+
+/// A call built by [`CallComposer`] with the hash and weight of every layer.
+#[derive(Debug)]
+pub struct ComposedCall {
+    pub call: EncodedCall,
+    /// From the innermost call outwards, the last layer is `call`.
+    pub layers: Vec<Layer>,
+}
+
+impl ComposedCall {
+    /// The hash of the outermost call.
+    pub fn call_hash(&self) -> [u8; 32] {
+        self.layers.last().map(|layer| layer.call_hash).unwrap_or_default()
+    }
+
+    /// The weight of the outermost call.
+    pub fn weight(&self) -> u64 {
+        self.layers.last().map(|layer| layer.weight).unwrap_or_default()
+    }
+
+    pub fn into_call(self) -> EncodedCall {
+        self.call
+    }
+}
+
+enum Wrapper {
+    Proxy {
+        real: AccountId32,
+        force_proxy_type: Option<ProxyType>,
+    },
+    AsMulti {
+        multisig: MultisigAccount,
+        signatory: AccountId32,
+    },
+}
+
+/// Nests calls in `proxy.proxy`, `multisig.as_multi` and `utility.batch_all`, e.g. for a multisig
+/// controlling a proxy:
+///
+/// ```ignore
+/// let composed = CallComposer::new(api.clone(), call)
+///     .proxy(treasury, None)
+///     .build()
+///     .await?;
+/// coordinator.propose(&signer, &composed.call).await?;
+/// ```
+///
+/// For multisigs within multisigs, the inner `as_multi` is the operation of the outer multisig.
+/// The `max_weight` of each `as_multi` is the weight of the call it wraps plus the
+/// [`fees::WEIGHT_MARGIN`], and its timepoint is looked up so the call hash matches what the
+/// signatory has to approve.
+pub struct CallComposer {
+    api: PolkadotRuntimeApi,
+    call: EncodedCall,
+    kind: LayerKind,
+    wrappers: Vec<Wrapper>,
+}
+
+impl CallComposer {
+    pub fn new(api: PolkadotRuntimeApi, call: EncodedCall) -> Self {
+        Self {
+            api,
+            call,
+            kind: LayerKind::Call,
+            wrappers: Vec::new(),
+        }
+    }
+
+    /// Starts with a `utility.batch_all` of the `calls`, which can be composed calls themselves.
+    pub fn batch_all(api: PolkadotRuntimeApi, calls: Vec<EncodedCall>) -> Self {
+        Self {
+            api,
+            kind: LayerKind::BatchAll { len: calls.len() },
+            call: EncodedCall::Utility(UtilityCall::batch_all { calls }),
+            wrappers: Vec::new(),
+        }
+    }
+
+    /// Dispatches the call as `real` from one of its proxies.
+    pub fn proxy(mut self, real: AccountId32, force_proxy_type: Option<ProxyType>) -> Self {
+        self.wrappers.push(Wrapper::Proxy { real, force_proxy_type });
+        self
+    }
+
+    /// Dispatches the call from the `multisig` account, approved by `signatory`.
+    pub fn as_multi(mut self, multisig: MultisigAccount, signatory: AccountId32) -> Self {
+        self.wrappers.push(Wrapper::AsMulti { multisig, signatory });
+        self
+    }
+
+    /// Wraps the call from the inside out, reading the weight of every layer with
+    /// `payment_queryInfo`.
+    pub async fn build(self) -> Result<ComposedCall, Box<dyn Error>> {
+        let mut call = self.call;
+        let mut layers = vec![Layer {
+            call_hash: multisig::call_hash(&call),
+            weight: fees::dispatch_weight(self.api.clone(), &call).await?,
+            kind: self.kind,
+        }];
+
+        for wrapper in self.wrappers {
+            let inner = layers.last().expect("there is at least one layer; qed");
+            let (kind, wrapped) = match wrapper {
+                Wrapper::Proxy { real, force_proxy_type } => (
+                    LayerKind::Proxy { real: real.clone() },
+                    EncodedCall::Proxy(ProxyCall::proxy {
+                        real,
+                        force_proxy_type,
+                        call: Box::new(call),
+                    }),
+                ),
+                Wrapper::AsMulti { multisig, signatory } => {
+                    let account = multisig.account_id();
+                    let other_signatories = multisig::other_signatories(&self.api, &multisig, &signatory)?;
+                    let (max_weight, wrapped) = if multisig.threshold() == 1 {
+                        (
+                            0,
+                            EncodedCall::Multisig(MultisigCall::as_multi_threshold_1 {
+                                other_signatories,
+                                call: Box::new(call),
+                            }),
+                        )
+                    } else {
+                        // later approvals need the timepoint of the first one, which changes the call hash
+                        let maybe_timepoint =
+                            multisig::get_timepoint(self.api.clone(), &account, &inner.call_hash).await?;
+                        let max_weight = fees::with_weight_margin(inner.weight);
+                        (
+                            max_weight,
+                            EncodedCall::Multisig(MultisigCall::as_multi {
+                                threshold: multisig.threshold(),
+                                other_signatories,
+                                maybe_timepoint,
+                                call: WrapperKeepOpaque::from_encoded(call.encode()),
+                                store_call: false,
+                                max_weight,
+                            }),
+                        )
+                    };
+                    (
+                        LayerKind::AsMulti {
+                            multisig: account,
+                            signatory,
+                            max_weight,
+                        },
+                        wrapped,
+                    )
+                }
+            };
+            call = wrapped;
+            layers.push(Layer {
+                call_hash: multisig::call_hash(&call),
+                weight: fees::dispatch_weight(self.api.clone(), &call).await?,
+                kind,
+            });
+        }
+
+        Ok(ComposedCall { call, layers })
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    dry_run::describe_dispatch_error, fees::FeeEstimator, polkadot, tx, EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
+};
+use std::error::Error;
+use subxt::{codec::Encode, sp_core::H256};
+
+type CouncilCall = polkadot::runtime_types::pallet_collective::pallet::Call;
+type ProposedEvent = polkadot::council::events::Proposed;
+type ExecutedEvent = polkadot::council::events::Executed;
+type DisapprovedEvent = polkadot::council::events::Disapproved;
+
+/// A call proposed to the council, see [`propose_motion`].
+#[derive(Clone, Debug)]
+pub struct Motion {
+    pub index: u32,
+    pub hash: H256,
+    /// Number of ayes needed to approve the motion.
+    pub threshold: u32,
+    /// Encoded length of the call.
+    pub length_bound: u32,
+    /// Weight of the call as reported by `payment_queryInfo`, needed to close the motion.
+    pub weight_bound: u64,
+}
+
+/// Proposes the `call` as a council motion, the proposer votes aye.
+///
+/// The collective executes motions with a threshold below 2 immediately with a single member as
+/// origin, which does not satisfy origins like `EnsureProportionAtLeast`, so they are rejected.
+pub async fn propose_motion(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    call: EncodedCall,
+    threshold: u32,
+) -> Result<Motion, Box<dyn Error>> {
+    if threshold < 2 {
+        return Err("A motion needs a threshold of at least 2".into());
+    }
+    let length_bound = call.encoded_size() as u32;
+    let weight_bound = FeeEstimator::new(api.clone()).estimate(&call, None).await?.weight;
+    let propose = EncodedCall::Council(CouncilCall::propose {
+        threshold,
+        proposal: Box::new(call),
+        length_bound,
+    });
+    let events = tx::sign_and_submit(api, propose, signer).await?;
+    let proposed = events.find_first::<ProposedEvent>()?.ok_or("Motion was not proposed")?;
+    Ok(Motion {
+        index: proposed.proposal_index,
+        hash: proposed.proposal_hash,
+        threshold,
+        length_bound,
+        weight_bound,
+    })
+}
+
+/// Votes on the `motion` as the `signer`, who has to be a council member.
+pub async fn vote_motion(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    motion: &Motion,
+    approve: bool,
+) -> Result<(), Box<dyn Error>> {
+    let vote = EncodedCall::Council(CouncilCall::vote {
+        proposal: motion.hash,
+        index: motion.index,
+        approve,
+    });
+    tx::sign_and_submit(api, vote, signer).await?;
+    Ok(())
+}
+
+/// Closes the `motion` once it has enough votes, executing the call if it was approved.
+pub async fn close_motion(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    motion: &Motion,
+) -> Result<(), Box<dyn Error>> {
+    let close = EncodedCall::Council(CouncilCall::close {
+        proposal_hash: motion.hash,
+        index: motion.index,
+        proposal_weight_bound: motion.weight_bound,
+        length_bound: motion.length_bound,
+    });
+    let events = tx::sign_and_submit(api.clone(), close, signer).await?;
+
+    if events.find_first::<DisapprovedEvent>()?.is_some() {
+        return Err(format!("Motion {} was disapproved", motion.index).into());
+    }
+    // the close succeeds even if the call fails, the result is in the `Executed` event
+    match events.find_first::<ExecutedEvent>()? {
+        Some(ExecutedEvent { result: Err(err), .. }) => {
+            Err(format!("Motion call failed: {}", describe_dispatch_error(&api, &err)).into())
+        }
+        Some(_) => Ok(()),
+        None => Err(format!("Motion {} was not executed", motion.index).into()),
+    }
+}
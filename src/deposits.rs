@@ -0,0 +1,97 @@
+//! Deposits reserved by pallets, calculated from the constants in the metadata.
+//!
+//! Source: https://github.com/paritytech/substrate/tree/polkadot-v0.9.18/frame
+
+use crate::PolkadotRuntimeApi;
+use std::error::Error;
+use subxt::codec::Decode;
+
+/// Bond for a `treasury.propose_spend` of `value`, a fraction of the value clamped to the minimum
+/// and maximum.
+pub fn treasury_proposal_bond(api: &PolkadotRuntimeApi, value: u128) -> Result<u128, Box<dyn Error>> {
+    let constants = api.constants().treasury();
+    let bond = (constants.proposal_bond()? * value).max(constants.proposal_bond_minimum()?);
+    Ok(match constants.proposal_bond_maximum()? {
+        Some(maximum) => bond.min(maximum),
+        None => bond,
+    })
+}
+
+/// Deposit for a `bounties.propose_bounty` with a `description` of this length in bytes.
+pub fn bounty_deposit(api: &PolkadotRuntimeApi, description_len: usize) -> Result<u128, Box<dyn Error>> {
+    let constants = api.constants().bounties();
+    Ok(constants.bounty_deposit_base()? + constants.data_deposit_per_byte()? * description_len as u128)
+}
+
+/// Deposit of a curator which accepts a bounty with this `fee`.
+pub fn bounty_curator_deposit(api: &PolkadotRuntimeApi, fee: u128) -> Result<u128, Box<dyn Error>> {
+    Ok(api.constants().bounties().bounty_curator_deposit()? * fee)
+}
+
+/// Deposit for a `tips.report_awesome` with a `reason` of this length in bytes.
+///
+/// Tips opened by tippers with `tip_new` do not take a deposit.
+pub fn tip_report_deposit(api: &PolkadotRuntimeApi, reason_len: usize) -> Result<u128, Box<dyn Error>> {
+    let constants = api.constants().tips();
+    Ok(constants.tip_report_deposit_base()? + constants.data_deposit_per_byte()? * reason_len as u128)
+}
+
+/// Deposit for an `identity.set_identity` with this many `additional_fields`.
+pub fn identity_deposit(api: &PolkadotRuntimeApi, additional_fields: u32) -> Result<u128, Box<dyn Error>> {
+    let constants = api.constants().identity();
+    Ok(constants.basic_deposit()? + constants.field_deposit()? * additional_fields as u128)
+}
+
+/// Deposit for an `identity.set_subs` with this many sub accounts.
+pub fn sub_accounts_deposit(api: &PolkadotRuntimeApi, sub_accounts: u32) -> Result<u128, Box<dyn Error>> {
+    Ok(api.constants().identity().sub_account_deposit()? * sub_accounts as u128)
+}
+
+/// Deposit the first approver of a multisig operation with this `threshold` reserves.
+pub fn multisig_deposit(api: &PolkadotRuntimeApi, threshold: u16) -> Result<u128, Box<dyn Error>> {
+    let constants = api.constants().multisig();
+    Ok(constants.deposit_base()? + constants.deposit_factor()? * threshold as u128)
+}
+
+/// Deposit for an account with this many `proxies`.
+pub fn proxy_deposit(api: &PolkadotRuntimeApi, proxies: u32) -> Result<u128, Box<dyn Error>> {
+    if proxies == 0 {
+        return Ok(0);
+    }
+    let constants = api.constants().proxy();
+    Ok(constants.proxy_deposit_base()? + constants.proxy_deposit_factor()? * proxies as u128)
+}
+
+/// Deposit for a proxy with this many pending `announcements`.
+pub fn announcement_deposit(api: &PolkadotRuntimeApi, announcements: u32) -> Result<u128, Box<dyn Error>> {
+    if announcements == 0 {
+        return Ok(0);
+    }
+    let constants = api.constants().proxy();
+    Ok(constants.announcement_deposit_base()? + constants.announcement_deposit_factor()? * announcements as u128)
+}
+
+/// Deposit for a `preimage.note_preimage` of this length in bytes.
+///
+/// The `Preimage` pallet in the embedded metadata does not expose `BaseDeposit` and `ByteDeposit`,
+/// so this only works if the connected runtime does.
+pub fn preimage_deposit(api: &PolkadotRuntimeApi, preimage_len: usize) -> Result<u128, Box<dyn Error>> {
+    let base_deposit = dynamic_constant(api, "Preimage", "BaseDeposit")?;
+    let byte_deposit = dynamic_constant(api, "Preimage", "ByteDeposit")?;
+    Ok(base_deposit + byte_deposit * preimage_len as u128)
+}
+
+/// Reads a balance constant which is not in the embedded metadata from the connected runtime.
+fn dynamic_constant(
+    api: &PolkadotRuntimeApi,
+    pallet: &'static str,
+    constant: &'static str,
+) -> Result<u128, Box<dyn Error>> {
+    let locked_metadata = api.client.metadata();
+    let metadata = locked_metadata.read();
+    let constant = metadata
+        .pallet(pallet)
+        .and_then(|metadata| metadata.constant(constant))
+        .map_err(|_| format!("The connected runtime does not expose `{pallet}::{constant}`"))?;
+    Ok(u128::decode(&mut &constant.value[..])?)
+}
@@ -0,0 +1,127 @@
+use crate::{
+    tx::{self, TxEvents, TxProgress},
+    EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
+};
+use std::{error::Error, fmt};
+use subxt::{
+    sp_core::H256,
+    sp_runtime::{
+        transaction_validity::{InvalidTransaction, TransactionValidityError},
+        DispatchError,
+    },
+    ModuleError, ModuleErrorData,
+};
+
+/// Reason a dry-run extrinsic would fail.
+#[derive(Debug)]
+pub enum DryRunError {
+    /// The extrinsic would not be included in a block, e.g. a stale nonce or no funds to pay fees.
+    Invalid(TransactionValidityError),
+    /// The extrinsic would be included (and pay fees) but a pallet rejects the call.
+    Module(ModuleError),
+    /// The extrinsic would be included (and pay fees) but the dispatch fails, e.g. `BadOrigin`.
+    Dispatch(DispatchError),
+}
+
+impl fmt::Display for DryRunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(err) => write!(f, "Transaction is invalid: {err:?}"),
+            Self::Module(err) => write!(f, "Dispatch failed: {}::{}", err.pallet, err.error),
+            Self::Dispatch(err) => write!(f, "Dispatch failed: {err:?}"),
+        }
+    }
+}
+
+impl Error for DryRunError {}
+
+/// Dry-runs the `signed_extrinsic` with `system_dryRun` at the given block (or the best block).
+///
+/// Module errors are resolved to the pallet and error names using the metadata.
+pub async fn dry_run(
+    api: PolkadotRuntimeApi,
+    signed_extrinsic: &[u8],
+    at: Option<H256>,
+) -> Result<Result<(), DryRunError>, Box<dyn Error>> {
+    Ok(match api.client.rpc().dry_run(signed_extrinsic, at).await? {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(DispatchError::Module(module_error))) => {
+            match resolve_module_error(&api, module_error.index, module_error.error) {
+                Some(module_error) => Err(DryRunError::Module(module_error)),
+                None => Err(DryRunError::Dispatch(DispatchError::Module(module_error))),
+            }
+        }
+        Ok(Err(err)) => Err(DryRunError::Dispatch(err)),
+        Err(err) => Err(DryRunError::Invalid(err)),
+    })
+}
+
+/// Submits extrinsics like [`tx::submit`] but dry-runs them first, so that a failing call is
+/// reported before any fees are paid.
+#[derive(Clone)]
+pub struct DryRunSubmitter {
+    api: PolkadotRuntimeApi,
+}
+
+impl DryRunSubmitter {
+    pub fn new(api: PolkadotRuntimeApi) -> Self {
+        Self { api }
+    }
+
+    /// Dry-runs the `extrinsic` and submits it if it would succeed.
+    ///
+    /// Extrinsics rejected with `InvalidTransaction::Future` are submitted anyway, since a nonce
+    /// ahead of the account (e.g. from a [`tx::NonceManager`]) only becomes valid once the earlier
+    /// extrinsics are included, so their calls are not checked.
+    pub async fn submit(&self, extrinsic: Vec<u8>) -> Result<TxProgress<'_>, Box<dyn Error>> {
+        match dry_run(self.api.clone(), &extrinsic, None).await? {
+            Ok(()) | Err(DryRunError::Invalid(TransactionValidityError::Invalid(InvalidTransaction::Future))) => (),
+            Err(err) => return Err(err.into()),
+        }
+        tx::submit(&self.api, extrinsic).await
+    }
+
+    /// Dry-runs and submits the `extrinsic`, waiting for it to be finalized successfully.
+    pub async fn submit_and_watch(&self, extrinsic: Vec<u8>) -> Result<TxEvents, Box<dyn Error>> {
+        Ok(self.submit(extrinsic).await?.wait_for_finalized_success().await?)
+    }
+
+    /// Signs, dry-runs and submits the `call`, see [`tx::sign_and_submit`].
+    pub async fn sign_and_submit(
+        &self,
+        call: EncodedCall,
+        signer: &PolkadotSigner,
+    ) -> Result<TxEvents, Box<dyn Error>> {
+        let extrinsic = tx::create_signed(self.api.clone(), &call, signer, None).await?;
+        self.submit_and_watch(extrinsic).await
+    }
+}
+
+/// Looks up the pallet and error names of a module error in the metadata.
+pub(crate) fn resolve_module_error(api: &PolkadotRuntimeApi, pallet_index: u8, error_index: u8) -> Option<ModuleError> {
+    let locked_metadata = api.client.metadata();
+    let metadata = locked_metadata.read();
+    let details = metadata.error(pallet_index, error_index).ok()?;
+    Some(ModuleError {
+        pallet: details.pallet().to_string(),
+        error: details.error().to_string(),
+        description: details.description().to_vec(),
+        error_data: ModuleErrorData {
+            pallet_index,
+            error: [error_index, 0, 0, 0],
+        },
+    })
+}
+
+/// Formats a dispatch error as `Pallet::Error` if it is a known module error.
+pub(crate) fn describe_dispatch_error(api: &PolkadotRuntimeApi, err: &DispatchError) -> String {
+    match err {
+        DispatchError::Module(module_error) => {
+            match resolve_module_error(api, module_error.index, module_error.error) {
+                Some(module_error) => format!("{}::{}", module_error.pallet, module_error.error),
+                None => format!("{err:?}"),
+            }
+        }
+        err => format!("{err:?}"),
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    accounts::{self, AccountInfo},
+    address::{self, Address},
+    balance::Token,
+    PolkadotRuntimeApi,
+};
+use std::{error::Error, fmt};
+use subxt::{sp_core::H256, sp_runtime::AccountId32};
+
+/// Why an account is flagged by the [`DustScanner`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Risk {
+    /// The free balance is within the margin of the existential deposit, or below it.
+    NearExistentialDeposit,
+    /// Other pallets (e.g. locks, proxies or session keys) depend on the account, so it cannot be
+    /// reaped until they are removed.
+    ConsumersBlockReaping,
+    /// Nothing provides for the account, it only exists because of `sufficients`.
+    NoProviders,
+}
+
+impl fmt::Display for Risk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NearExistentialDeposit => write!(f, "near existential deposit"),
+            Self::ConsumersBlockReaping => write!(f, "consumers block reaping"),
+            Self::NoProviders => write!(f, "no providers"),
+        }
+    }
+}
+
+/// Flags the risks of a single account, see [`Risk`].
+pub fn assess(info: &AccountInfo, existential_deposit: u128, margin: u128) -> Vec<Risk> {
+    let mut risks = Vec::new();
+    if info.data.free <= existential_deposit.saturating_add(margin) {
+        risks.push(Risk::NearExistentialDeposit);
+    }
+    if info.consumers > 0 {
+        risks.push(Risk::ConsumersBlockReaping);
+    }
+    if info.providers == 0 {
+        risks.push(Risk::NoProviders);
+    }
+    risks
+}
+
+#[derive(Clone, Debug)]
+pub struct AccountRisk {
+    pub account: AccountId32,
+    pub info: AccountInfo,
+    pub risks: Vec<Risk>,
+}
+
+/// Accounts with at least one [`Risk`], see [`DustScanner::scan`].
+#[derive(Clone, Debug)]
+pub struct DustReport {
+    pub block_hash: H256,
+    pub block_number: u32,
+    pub token: Token,
+    pub ss58_prefix: u16,
+    pub existential_deposit: u128,
+    pub margin: u128,
+    /// Number of accounts scanned.
+    pub accounts: usize,
+    pub at_risk: Vec<AccountRisk>,
+}
+
+impl DustReport {
+    /// The flagged accounts with the given `risk`.
+    pub fn with_risk(&self, risk: Risk) -> impl Iterator<Item = &AccountRisk> {
+        self.at_risk.iter().filter(move |account| account.risks.contains(&risk))
+    }
+}
+
+impl fmt::Display for DustReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "block: #{} ({:?})", self.block_number, self.block_hash)?;
+        writeln!(
+            f,
+            "existential deposit: {}, margin: {}",
+            self.token.balance(self.existential_deposit),
+            self.token.balance(self.margin)
+        )?;
+        writeln!(f, "flagged {} of {} accounts", self.at_risk.len(), self.accounts)?;
+        for AccountRisk { account, info, risks } in &self.at_risk {
+            let risks = risks.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+            writeln!(
+                f,
+                "{:<48}  {:>24}  consumers: {}, providers: {}  {risks}",
+                Address::new(account.clone(), self.ss58_prefix).to_string(),
+                self.token.balance(info.data.free).to_string(),
+                info.consumers,
+                info.providers
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Scans every `system.account` for dust and accounts which are at risk of being reaped.
+#[derive(Clone)]
+pub struct DustScanner {
+    api: PolkadotRuntimeApi,
+    margin: Option<u128>,
+    at: Option<H256>,
+}
+
+impl DustScanner {
+    /// By default the margin is the existential deposit, so free balances up to twice the
+    /// existential deposit are flagged.
+    pub fn new(api: PolkadotRuntimeApi) -> Self {
+        Self {
+            api,
+            margin: None,
+            at: None,
+        }
+    }
+
+    /// Flags free balances up to `margin` above the existential deposit.
+    pub fn margin(mut self, margin: u128) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    /// Scans at the given block instead of the finalized head.
+    pub fn at(mut self, block_hash: H256) -> Self {
+        self.at = Some(block_hash);
+        self
+    }
+
+    pub async fn scan(&self) -> Result<DustReport, Box<dyn Error>> {
+        let block_hash = accounts::pin_block(&self.api, self.at).await?;
+        let existential_deposit = self.api.constants().balances().existential_deposit()?;
+        let margin = self.margin.unwrap_or(existential_deposit);
+        let all_accounts = accounts::get_all_accounts(&self.api, block_hash).await?;
+
+        Ok(DustReport {
+            block_hash,
+            block_number: accounts::block_number(&self.api, block_hash).await?,
+            token: Token::from_chain(&self.api).await?,
+            ss58_prefix: address::chain_prefix(&self.api)?,
+            existential_deposit,
+            margin,
+            accounts: all_accounts.len(),
+            at_risk: all_accounts
+                .into_iter()
+                .filter_map(|(account, info)| {
+                    let risks = assess(&info, existential_deposit, margin);
+                    (!risks.is_empty()).then_some(AccountRisk { account, info, risks })
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    balance::{Balance, Token},
+    signer::DummySigner,
+    tx, EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
+};
+use serde::{Deserialize, Deserializer};
+use sp_rpc::number::NumberOrHex;
+use std::error::Error;
+use subxt::{
+    rpc::{rpc_params, ClientT},
+    sp_core::H256,
+    sp_runtime::{AccountId32, Percent},
+    PolkadotExtrinsicParamsBuilder,
+};
+
+/// Added to the dispatch weight by [`max_weight`], the weight of a call can change between the
+/// estimate and the execution, e.g. with the number of approvals or the size of storage items.
+pub const WEIGHT_MARGIN: Percent = Percent::from_percent(10);
+
+/// Response of `payment_queryFeeDetails`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeDetails<Balance> {
+    pub inclusion_fee: Option<InclusionFee<Balance>>,
+    #[serde(skip)]
+    pub tip: Balance,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionFee<Balance> {
+    pub base_fee: Balance,
+    pub len_fee: Balance,
+    pub adjusted_weight_fee: Balance,
+}
+
+impl InclusionFee<NumberOrHex> {
+    pub fn inclusion_fee(&self) -> u128 {
+        let base_fee = self.base_fee.into_u256();
+        let len_fee = self.len_fee.into_u256();
+        let adjusted_weight_fee = self.adjusted_weight_fee.into_u256();
+        (base_fee + len_fee + adjusted_weight_fee).as_u128()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DispatchClass {
+    Normal,
+    Operational,
+    Mandatory,
+}
+
+/// Response of `payment_queryInfo`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDispatchInfo {
+    pub weight: u64,
+    pub class: DispatchClass,
+    #[serde(deserialize_with = "deserialize_balance")]
+    pub partial_fee: u128,
+}
+
+/// Balances are serialized as decimal strings (to not overflow in JavaScript) but accept numbers too.
+fn deserialize_balance<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Balance {
+        Number(u128),
+        String(String),
+    }
+
+    match Balance::deserialize(deserializer)? {
+        Balance::Number(value) => Ok(value),
+        Balance::String(value) => match value.strip_prefix("0x") {
+            Some(hex) => u128::from_str_radix(hex, 16),
+            None => value.parse(),
+        }
+        .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Everything the runtime charges for an extrinsic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeBreakdown {
+    pub weight: u64,
+    pub class: DispatchClass,
+    /// The fee excluding the tip, this is what `payment_queryInfo` reports.
+    pub partial_fee: u128,
+    pub base_fee: u128,
+    pub len_fee: u128,
+    pub adjusted_weight_fee: u128,
+    pub tip: u128,
+}
+
+impl FeeBreakdown {
+    pub fn inclusion_fee(&self) -> u128 {
+        self.base_fee + self.len_fee + self.adjusted_weight_fee
+    }
+
+    /// The amount withdrawn from the sender.
+    pub fn total(&self) -> u128 {
+        self.inclusion_fee() + self.tip
+    }
+}
+
+/// Estimates fees for calls in the `polkadot` module using the `transaction_payment` RPCs.
+#[derive(Clone)]
+pub struct FeeEstimator {
+    api: PolkadotRuntimeApi,
+    tip: u128,
+    at: Option<H256>,
+}
+
+impl FeeEstimator {
+    pub fn new(api: PolkadotRuntimeApi) -> Self {
+        Self { api, tip: 0, at: None }
+    }
+
+    /// Includes a `tip` in the estimated extrinsics.
+    pub fn tip(mut self, tip: u128) -> Self {
+        self.tip = tip;
+        self
+    }
+
+    /// Estimates at the given block instead of the best block.
+    pub fn at(mut self, block_hash: H256) -> Self {
+        self.at = Some(block_hash);
+        self
+    }
+
+    /// Estimates the fees for the `call`, signed by `signer` if available or otherwise with a dummy
+    /// signature from an empty account.
+    pub async fn estimate(
+        &self,
+        call: &EncodedCall,
+        signer: Option<&PolkadotSigner>,
+    ) -> Result<FeeBreakdown, Box<dyn Error>> {
+        let params = PolkadotExtrinsicParamsBuilder::new().tip(self.tip);
+        let encoded_signed = match signer {
+            Some(signer) => tx::create_signed_with_params(self.api.clone(), call, signer, None, params).await?,
+            None => {
+                let mut signer = DummySigner::new(AccountId32::new([0u8; 32]));
+                signer.set_nonce(0);
+                tx::create_signed_with_params(self.api.clone(), call, &signer, None, params).await?
+            }
+        };
+        self.estimate_signed(&encoded_signed).await
+    }
+
+    /// Estimates the fees for the `call` as if it was signed by `sender`, without needing its key.
+    ///
+    /// The extrinsic is signed with a dummy `sr25519` signature using the sender's next nonce, so
+    /// the encoded length matches what `sender` would submit with an `sr25519` or `ed25519` key.
+    pub async fn estimate_for(&self, call: &EncodedCall, sender: AccountId32) -> Result<FeeBreakdown, Box<dyn Error>> {
+        let signer = DummySigner::new(sender);
+        let params = PolkadotExtrinsicParamsBuilder::new().tip(self.tip);
+        let encoded_signed = tx::create_signed_with_params(self.api.clone(), call, &signer, None, params).await?;
+        self.estimate_signed(&encoded_signed).await
+    }
+
+    /// Estimates the fees for an already signed extrinsic.
+    ///
+    /// The tip cannot be recovered from the encoded extrinsic so the configured tip is reported.
+    pub async fn estimate_signed(&self, encoded_signed: &[u8]) -> Result<FeeBreakdown, Box<dyn Error>> {
+        let encoded = format!("0x{}", hex::encode(encoded_signed));
+        let client = &self.api.client.rpc().client;
+        let info: RuntimeDispatchInfo = client
+            .request("payment_queryInfo", rpc_params![encoded.clone(), self.at])
+            .await?;
+        let details: FeeDetails<NumberOrHex> = client
+            .request("payment_queryFeeDetails", rpc_params![encoded, self.at])
+            .await?;
+
+        // unsigned or free (e.g. `Pays::No`) extrinsics have no inclusion fee
+        let (base_fee, len_fee, adjusted_weight_fee) = details
+            .inclusion_fee
+            .map(|fee| {
+                (
+                    fee.base_fee.into_u256().as_u128(),
+                    fee.len_fee.into_u256().as_u128(),
+                    fee.adjusted_weight_fee.into_u256().as_u128(),
+                )
+            })
+            .unwrap_or_default();
+
+        Ok(FeeBreakdown {
+            weight: info.weight,
+            class: info.class,
+            partial_fee: info.partial_fee,
+            base_fee,
+            len_fee,
+            adjusted_weight_fee,
+            tip: self.tip,
+        })
+    }
+}
+
+/// Estimates the total fee `sender` would pay for the `call`, e.g. to show the cost of a multisig
+/// approval before anyone signs.
+pub async fn estimate_fee(
+    api: PolkadotRuntimeApi,
+    call: &EncodedCall,
+    sender: AccountId32,
+) -> Result<Balance, Box<dyn Error>> {
+    let token = Token::from_chain(&api).await?;
+    Ok(token.balance(FeeEstimator::new(api).estimate_for(call, sender).await?.total()))
+}
+
+/// The dispatch weight of the `call`, from `payment_queryInfo` on an extrinsic signed with a dummy
+/// signature.
+///
+/// This is the weight of the call only, without the base weight of the extrinsic.
+pub async fn dispatch_weight(api: PolkadotRuntimeApi, call: &EncodedCall) -> Result<u64, Box<dyn Error>> {
+    Ok(FeeEstimator::new(api).estimate(call, None).await?.weight)
+}
+
+/// Adds the [`WEIGHT_MARGIN`] to a dispatch `weight`.
+pub fn with_weight_margin(weight: u64) -> u64 {
+    weight.saturating_add(WEIGHT_MARGIN * weight)
+}
+
+/// The `max_weight` to pass to `as_multi` for the `call`, its dispatch weight plus the
+/// [`WEIGHT_MARGIN`].
+pub async fn max_weight(api: PolkadotRuntimeApi, call: &EncodedCall) -> Result<u64, Box<dyn Error>> {
+    Ok(with_weight_margin(dispatch_weight(api, call).await?))
+}
@@ -0,0 +1,151 @@
+use crate::{
+    accounts::{self, AccountInfo},
+    address::{self, Address},
+    balance::Token,
+    PolkadotRuntimeApi,
+};
+use std::{cmp::Reverse, error::Error, fmt};
+use subxt::{
+    codec::Decode,
+    sp_core::{storage::StorageKey, twox_128, H256},
+    sp_runtime::AccountId32,
+};
+
+/// Free and reserved balance of a single account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Holder {
+    pub account: AccountId32,
+    pub free: u128,
+    pub reserved: u128,
+}
+
+impl Holder {
+    pub fn total(&self) -> u128 {
+        self.free + self.reserved
+    }
+}
+
+/// Comparison of the sum of all account balances against `balances.TotalIssuance`.
+#[derive(Clone, Debug)]
+pub struct IssuanceReport {
+    pub block_hash: H256,
+    pub block_number: u32,
+    pub token: Token,
+    pub ss58_prefix: u16,
+    pub accounts: usize,
+    pub total_free: u128,
+    pub total_reserved: u128,
+    pub total_issuance: u128,
+    /// `balances.InactiveIssuance`, `None` if the runtime does not have it.
+    pub inactive_issuance: Option<u128>,
+    /// Largest holders by free + reserved balance.
+    pub top_holders: Vec<Holder>,
+}
+
+impl IssuanceReport {
+    /// Sum of free and reserved balances over all accounts.
+    pub fn total_balance(&self) -> u128 {
+        self.total_free + self.total_reserved
+    }
+
+    /// `TotalIssuance` minus the sum of all balances, this should be zero.
+    pub fn discrepancy(&self) -> i128 {
+        self.total_issuance as i128 - self.total_balance() as i128
+    }
+}
+
+impl fmt::Display for IssuanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = &self.token;
+        writeln!(f, "block: #{} ({:?})", self.block_number, self.block_hash)?;
+        writeln!(f, "accounts: {}", self.accounts)?;
+        writeln!(f, "free: {}", token.balance(self.total_free))?;
+        writeln!(f, "reserved: {}", token.balance(self.total_reserved))?;
+        writeln!(f, "total issuance: {}", token.balance(self.total_issuance))?;
+        if let Some(inactive_issuance) = self.inactive_issuance {
+            writeln!(f, "inactive issuance: {}", token.balance(inactive_issuance))?;
+        }
+        let discrepancy = self.discrepancy();
+        let sign = if discrepancy < 0 { "-" } else { "" };
+        writeln!(f, "discrepancy: {sign}{}", token.balance(discrepancy.unsigned_abs()))?;
+        for (rank, holder) in self.top_holders.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>5}  {:<48}  {:>24}",
+                rank + 1,
+                Address::new(holder.account.clone(), self.ss58_prefix).to_string(),
+                token.balance(holder.total()).to_string()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads `balances.InactiveIssuance` if the runtime has it, newer runtimes track funds which are
+/// not part of the active supply (e.g. crowdloans) there.
+async fn inactive_issuance(api: &PolkadotRuntimeApi, block_hash: H256) -> Result<Option<u128>, Box<dyn Error>> {
+    let exists = {
+        let locked_metadata = api.client.metadata();
+        let metadata = locked_metadata.read();
+        metadata
+            .runtime_metadata()
+            .pallets
+            .iter()
+            .filter(|pallet| pallet.name == "Balances")
+            .filter_map(|pallet| pallet.storage.as_ref())
+            .flat_map(|storage| storage.entries.iter())
+            .any(|entry| entry.name == "InactiveIssuance")
+    };
+    if !exists {
+        return Ok(None);
+    }
+
+    let key = [twox_128(b"Balances"), twox_128(b"InactiveIssuance")].concat();
+    match api
+        .client
+        .storage()
+        .fetch_raw(StorageKey(key), Some(block_hash))
+        .await?
+    {
+        Some(data) => Ok(Some(u128::decode(&mut &data.0[..])?)),
+        None => Ok(Some(0)),
+    }
+}
+
+/// Sums free + reserved over every `system.account` at `at` (or the finalized head) and compares
+/// it against the total issuance, returning the `top_n` holders.
+pub async fn reconcile_issuance(
+    api: PolkadotRuntimeApi,
+    at: Option<H256>,
+    top_n: usize,
+) -> Result<IssuanceReport, Box<dyn Error>> {
+    let block_hash = accounts::pin_block(&api, at).await?;
+    let all_accounts = accounts::get_all_accounts(&api, block_hash).await?;
+
+    let mut holders: Vec<Holder> = all_accounts
+        .into_iter()
+        .map(|(account, AccountInfo { data, .. })| Holder {
+            account,
+            free: data.free,
+            reserved: data.reserved,
+        })
+        .collect();
+    let accounts = holders.len();
+    let total_free = holders.iter().map(|holder| holder.free).sum();
+    let total_reserved = holders.iter().map(|holder| holder.reserved).sum();
+    holders.sort_by_key(|holder| Reverse(holder.total()));
+    holders.truncate(top_n);
+
+    Ok(IssuanceReport {
+        block_hash,
+        block_number: accounts::block_number(&api, block_hash).await?,
+        token: Token::from_chain(&api).await?,
+        ss58_prefix: address::chain_prefix(&api)?,
+        accounts,
+        total_free,
+        total_reserved,
+        total_issuance: api.storage().balances().total_issuance(Some(block_hash)).await?,
+        inactive_issuance: inactive_issuance(&api, block_hash).await?,
+        top_holders: holders,
+    })
+}
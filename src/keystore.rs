@@ -0,0 +1,191 @@
+use crate::{
+    signer::{EcdsaSigner, Ed25519Signer, Sr25519Signer},
+    PolkadotSigner,
+};
+use crypto_secretbox::{aead::Aead, KeyInit, XSalsa20Poly1305};
+use serde::Deserialize;
+use std::{error::Error, path::Path};
+use subxt::{
+    sp_core::{crypto::Ss58Codec, ecdsa, ed25519, Pair},
+    sp_runtime::AccountId32,
+};
+
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+const SCRYPT_LENGTH: usize = 32 + 3 * 4;
+const NONCE_LENGTH: usize = 24;
+
+/// Signature scheme of a key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum KeyType {
+    #[default]
+    Sr25519,
+    Ed25519,
+    Ecdsa,
+}
+
+impl std::str::FromStr for KeyType {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sr25519" => Ok(Self::Sr25519),
+            "ed25519" => Ok(Self::Ed25519),
+            "ecdsa" => Ok(Self::Ecdsa),
+            _ => Err(format!("Unsupported key type: {s}").into()),
+        }
+    }
+}
+
+fn pair_from_string<P: Pair>(suri: &str, password: Option<&str>) -> Result<P, Box<dyn Error>> {
+    P::from_string(suri, password).map_err(|err| format!("Invalid secret: {err:?}").into())
+}
+
+fn pair_from_seed<P: Pair>(seed: &[u8]) -> Result<P, Box<dyn Error>> {
+    P::from_seed_slice(seed).map_err(|err| format!("Invalid secret: {err:?}").into())
+}
+
+/// Loads a signer from a secret URI such as `//Alice`, `0x{seed}` or `{phrase}//hard/soft///password`.
+pub fn from_uri(suri: &str, key_type: KeyType) -> Result<Box<PolkadotSigner>, Box<dyn Error>> {
+    from_uri_with_password(suri, None, key_type)
+}
+
+fn from_uri_with_password(
+    suri: &str,
+    password: Option<&str>,
+    key_type: KeyType,
+) -> Result<Box<PolkadotSigner>, Box<dyn Error>> {
+    Ok(match key_type {
+        KeyType::Sr25519 => Box::new(Sr25519Signer::new(pair_from_string(suri, password)?)),
+        KeyType::Ed25519 => Box::new(Ed25519Signer::new(pair_from_string(suri, password)?)),
+        KeyType::Ecdsa => Box::new(EcdsaSigner::new(pair_from_string(suri, password)?)),
+    })
+}
+
+/// Loads a signer from a BIP39 `phrase`, the `derivation_path` (e.g. `//polkadot//0`) may be empty.
+pub fn from_mnemonic(
+    phrase: &str,
+    derivation_path: &str,
+    password: Option<&str>,
+    key_type: KeyType,
+) -> Result<Box<PolkadotSigner>, Box<dyn Error>> {
+    if !(derivation_path.is_empty() || derivation_path.starts_with('/')) {
+        return Err(format!("Invalid derivation path: {derivation_path}").into());
+    }
+    if phrase.split_whitespace().count() < 12 {
+        return Err("Mnemonic must have at least 12 words".into());
+    }
+    from_uri_with_password(&format!("{phrase}{derivation_path}"), password, key_type)
+}
+
+#[derive(Debug, Deserialize)]
+struct EncryptedJson {
+    address: String,
+    encoded: String,
+    encoding: EncryptedJsonEncoding,
+}
+
+#[derive(Debug, Deserialize)]
+struct EncryptedJsonEncoding {
+    content: Vec<String>,
+    #[serde(rename = "type")]
+    kind: Vec<String>,
+}
+
+/// Loads a signer from a polkadot-js account export, as produced by "Export Account" in the UI.
+pub fn from_json(json: &str, password: &str) -> Result<Box<PolkadotSigner>, Box<dyn Error>> {
+    let json: EncryptedJson = serde_json::from_str(json)?;
+    let key_type: KeyType = json
+        .encoding
+        .content
+        .get(1)
+        .ok_or("Missing key type in encoding")?
+        .parse()?;
+
+    let encoded = match json.encoded.strip_prefix("0x") {
+        Some(hex) => hex::decode(hex)?,
+        None => base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &json.encoded)?,
+    };
+    let decrypted = if json.encoding.kind.iter().any(|kind| kind == "xsalsa20-poly1305") {
+        decrypt(
+            &encoded,
+            password,
+            json.encoding.kind.iter().any(|kind| kind == "scrypt"),
+        )?
+    } else {
+        encoded
+    };
+    let (secret, public) = decode_pkcs8(&decrypted).ok_or("Invalid PKCS8 key, wrong password?")?;
+
+    let signer: Box<PolkadotSigner> = match key_type {
+        KeyType::Sr25519 => {
+            // polkadot-js stores the secret in the ed25519-compatible form used by the wasm bindings
+            let secret = schnorrkel::SecretKey::from_ed25519_bytes(secret)
+                .map_err(|err| format!("Invalid sr25519 secret: {err}"))?;
+            Box::new(Sr25519Signer::new(pair_from_seed(&secret.to_bytes())?))
+        }
+        KeyType::Ed25519 => Box::new(Ed25519Signer::new(pair_from_seed::<ed25519::Pair>(&secret[..32])?)),
+        KeyType::Ecdsa => Box::new(EcdsaSigner::new(pair_from_seed::<ecdsa::Pair>(&secret[..32])?)),
+    };
+
+    // ecdsa accounts are hashed so only sr25519 and ed25519 can be compared to the public key
+    if matches!(key_type, KeyType::Sr25519 | KeyType::Ed25519) && AsRef::<[u8]>::as_ref(signer.account_id()) != public {
+        return Err("Decoded public key does not match".into());
+    }
+    if let Ok(address) = AccountId32::from_ss58check(&json.address) {
+        if &address != signer.account_id() {
+            return Err(format!("Decoded key does not match address {}", json.address).into());
+        }
+    }
+    Ok(signer)
+}
+
+/// Reads a polkadot-js account export from disk, see [`from_json`].
+pub fn from_json_file<P: AsRef<Path>>(path: P, password: &str) -> Result<Box<PolkadotSigner>, Box<dyn Error>> {
+    from_json(&std::fs::read_to_string(path)?, password)
+}
+
+/// Source: https://github.com/polkadot-js/common/blob/master/packages/util-crypto/src/json/decryptData.ts
+fn decrypt(encrypted: &[u8], password: &str, use_scrypt: bool) -> Result<Vec<u8>, Box<dyn Error>> {
+    let (key, encrypted) = if use_scrypt {
+        if encrypted.len() < SCRYPT_LENGTH {
+            return Err("Encrypted data is too short".into());
+        }
+        let salt = &encrypted[..32];
+        let param = |i: usize| u32::from_le_bytes(encrypted[32 + i * 4..36 + i * 4].try_into().unwrap());
+        let (n, p, r) = (param(0), param(1), param(2));
+        if !n.is_power_of_two() {
+            return Err(format!("Invalid scrypt N: {n}").into());
+        }
+        let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p, 32)
+            .map_err(|err| format!("Invalid scrypt params: {err}"))?;
+        let mut key = [0u8; 32];
+        scrypt::scrypt(password.as_bytes(), salt, &params, &mut key).map_err(|err| format!("Scrypt failed: {err}"))?;
+        (key, &encrypted[SCRYPT_LENGTH..])
+    } else {
+        // legacy exports use the password directly, truncated or zero-padded to 32 bytes
+        let mut key = [0u8; 32];
+        let len = password.len().min(32);
+        key[..len].copy_from_slice(&password.as_bytes()[..len]);
+        (key, encrypted)
+    };
+    if encrypted.len() < NONCE_LENGTH {
+        return Err("Encrypted data is too short".into());
+    }
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+    XSalsa20Poly1305::new(&key.into())
+        .decrypt(nonce.into(), ciphertext)
+        .map_err(|_| "Unable to decode using the supplied passphrase".into())
+}
+
+/// Splits the decrypted PKCS8 document into the secret and public key.
+///
+/// Source: https://github.com/polkadot-js/common/blob/master/packages/keyring/src/pair/decode.ts
+fn decode_pkcs8(decoded: &[u8]) -> Option<(&[u8], &[u8])> {
+    let body = decoded.strip_prefix(&PKCS8_HEADER[..])?;
+    // secrets are either 64 bytes (sr25519, ed25519) or a 32 byte seed (ecdsa)
+    [64, 32].into_iter().find_map(|secret_length| {
+        let (secret, rest) = body.split_at(secret_length.min(body.len()));
+        Some((secret, rest.strip_prefix(&PKCS8_DIVIDER[..])?))
+    })
+}
@@ -1,12 +1,35 @@
 use std::{error::Error, future::Future};
-use subxt::{ClientBuilder, DefaultConfig, PolkadotExtrinsicParams};
+use subxt::{extrinsic::Signer, ClientBuilder, DefaultConfig, PolkadotExtrinsicParams};
+
+pub mod accounts;
+pub mod address;
+pub mod balance;
+pub mod batch;
+pub mod bounties;
+pub mod compose;
+pub mod council;
+pub mod deposits;
+pub mod dry_run;
+pub mod dust;
+pub mod fees;
+pub mod issuance;
+pub mod keystore;
+pub mod multisig;
+pub mod payout;
+pub mod payout_import;
+pub mod signer;
+pub mod stats;
+pub mod tips;
+pub mod transfer;
+pub mod treasury;
+pub mod tx;
 
 #[subxt::subxt(
     runtime_metadata_path = "polkadot_metadata.scale",
     derive_for_all_types = "Debug",
     derive_for_type(type = "sp_version::RuntimeVersion", derive = "Eq, PartialEq"),
-    derive_for_type(type = "frame_system::AccountInfo", derive = "Eq, PartialEq"),
-    derive_for_type(type = "pallet_balances::AccountData", derive = "Eq, PartialEq"),
+    derive_for_type(type = "frame_system::AccountInfo", derive = "Clone, Eq, PartialEq"),
+    derive_for_type(type = "pallet_balances::AccountData", derive = "Clone, Eq, PartialEq"),
     derive_for_type(type = "pallet_treasury::Proposal", derive = "Eq, PartialEq"),
     derive_for_type(type = "sp_version::RuntimeVersion", derive = "Default")
 )]
@@ -20,6 +43,9 @@ pub mod polkadot {
 
 pub type PolkadotRuntimeApi = polkadot::RuntimeApi<DefaultConfig, PolkadotExtrinsicParams<DefaultConfig>>;
 
+/// Any key type which can sign for the helpers, see [`signer`] for the available implementations.
+pub type PolkadotSigner = dyn Signer<DefaultConfig> + Send + Sync;
+
 pub type EncodedCall = polkadot::runtime_types::polkadot_runtime::Call;
 
 pub async fn with_default_client<F, R>(f: F) -> Result<(), Box<dyn Error>>
@@ -0,0 +1,526 @@
+use crate::{
+    accounts, address::Address, dry_run::describe_dispatch_error, fees, polkadot, tx, EncodedCall, PolkadotRuntimeApi,
+    PolkadotSigner,
+};
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use subxt::{
+    codec::{Decode, Encode},
+    sp_core::{blake2_256, storage::StorageKey, twox_128, twox_64, H256},
+    sp_runtime::{traits::TrailingZeroInput, AccountId32},
+    WrapperKeepOpaque,
+};
+
+pub type Timepoint = polkadot::runtime_types::pallet_multisig::Timepoint<u32>;
+pub type Multisig = polkadot::runtime_types::pallet_multisig::Multisig<u32, u128, AccountId32>;
+
+type MultisigCall = polkadot::runtime_types::pallet_multisig::pallet::Call;
+type ProxyCall = polkadot::runtime_types::pallet_proxy::pallet::Call;
+type UtilityCall = polkadot::runtime_types::pallet_utility::pallet::Call;
+type MultisigExecutedEvent = polkadot::multisig::events::MultisigExecuted;
+type UnreservedEvent = polkadot::balances::events::Unreserved;
+
+/// The hash the `multisig` pallet identifies a call by.
+pub fn call_hash(call: &EncodedCall) -> [u8; 32] {
+    blake2_256(&call.encode())
+}
+
+/// Derives the multisig account of the `signatories`, which have to be sorted, see
+/// [`MultisigAccount`].
+///
+/// Source: https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/frame/multisig/src/lib.rs
+pub fn multi_account_id(signatories: &[AccountId32], threshold: u16) -> AccountId32 {
+    let entropy = (b"modlpy/utilisuba", signatories, threshold).using_encoded(blake2_256);
+    Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+        .expect("infinite length input; no invalid inputs for type; qed")
+}
+
+/// The signatories and threshold of a multisig account.
+///
+/// The signatories are sorted and deduplicated, so the same set always derives the same account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigAccount {
+    signatories: Vec<AccountId32>,
+    threshold: u16,
+}
+
+impl MultisigAccount {
+    /// Validates the number of signatories against the `MaxSignatories` constant.
+    pub fn new(
+        api: &PolkadotRuntimeApi,
+        signatories: impl IntoIterator<Item = AccountId32>,
+        threshold: u16,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::with_max_signatories(signatories, threshold, api.constants().multisig().max_signatories()?)
+    }
+
+    /// Same as [`MultisigAccount::new`] without reading `MaxSignatories` from the chain.
+    pub fn with_max_signatories(
+        signatories: impl IntoIterator<Item = AccountId32>,
+        threshold: u16,
+        max_signatories: u16,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut signatories: Vec<AccountId32> = signatories.into_iter().collect();
+        signatories.sort();
+        signatories.dedup();
+        if signatories.len() < 2 {
+            return Err("A multisig needs at least 2 signatories".into());
+        }
+        if signatories.len() > max_signatories as usize {
+            return Err(format!("A multisig can have at most {max_signatories} signatories").into());
+        }
+        if threshold == 0 || threshold as usize > signatories.len() {
+            return Err(format!("Invalid threshold {threshold} for {} signatories", signatories.len()).into());
+        }
+        Ok(Self { signatories, threshold })
+    }
+
+    /// The sorted signatories.
+    pub fn signatories(&self) -> &[AccountId32] {
+        &self.signatories
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn is_signatory(&self, who: &AccountId32) -> bool {
+        self.signatories.binary_search(who).is_ok()
+    }
+
+    pub fn account_id(&self) -> AccountId32 {
+        multi_account_id(&self.signatories, self.threshold)
+    }
+
+    /// The SS58 address of the account with the `prefix`, see [`crate::address::chain_prefix`].
+    pub fn address(&self, prefix: u16) -> Address {
+        Address::new(self.account_id(), prefix)
+    }
+
+    /// The sorted signatories except `who`, as passed to the calls of `who`.
+    ///
+    /// Without the chain prefix the error does not name `who`, see [`other_signatories`].
+    pub fn other_signatories(&self, who: &AccountId32) -> Result<Vec<AccountId32>, Box<dyn Error>> {
+        if !self.is_signatory(who) {
+            return Err("Account is not a signatory".into());
+        }
+        Ok(self
+            .signatories
+            .iter()
+            .filter(|account| *account != who)
+            .cloned()
+            .collect())
+    }
+}
+
+/// [`MultisigAccount::other_signatories`] naming `who` by its address on the chain if it is not
+/// a signatory.
+pub fn other_signatories(
+    api: &PolkadotRuntimeApi,
+    multisig: &MultisigAccount,
+    who: &AccountId32,
+) -> Result<Vec<AccountId32>, Box<dyn Error>> {
+    if !multisig.is_signatory(who) {
+        return Err(format!("{} is not a signatory", Address::for_chain(api, who.clone())?).into());
+    }
+    multisig.other_signatories(who)
+}
+
+/// Returns the `Timepoint` of a pending operation from `multisig.Multisigs`, `None` if the
+/// `call_hash` has not been approved yet.
+pub async fn get_timepoint(
+    api: PolkadotRuntimeApi,
+    multisig_account: &AccountId32,
+    call_hash: &[u8; 32],
+) -> Result<Option<Timepoint>, Box<dyn Error>> {
+    let multisig = api
+        .storage()
+        .multisig()
+        .multisigs(multisig_account, call_hash, None)
+        .await?;
+    Ok(multisig.map(|multisig| multisig.when))
+}
+
+/// Cancels a pending operation as its depositor, returning the reclaimed deposit.
+///
+/// Only the depositor (the first approver) can cancel, which unreserves the deposit and any call
+/// data stored with `store_call`.
+pub async fn cancel_multisig(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    multisig: &MultisigAccount,
+    call_hash: [u8; 32],
+) -> Result<u128, Box<dyn Error>> {
+    let other_signatories = other_signatories(&api, multisig, signer.account_id())?;
+    let timepoint = get_timepoint(api.clone(), &multisig.account_id(), &call_hash)
+        .await?
+        .ok_or("No pending multisig operation for the call hash")?;
+
+    let cancel_as_multi = EncodedCall::Multisig(MultisigCall::cancel_as_multi {
+        threshold: multisig.threshold,
+        other_signatories,
+        timepoint,
+        call_hash,
+    });
+    let events = tx::sign_and_submit(api, cancel_as_multi, signer).await?;
+    let mut reclaimed = 0;
+    for event in events.find::<UnreservedEvent>() {
+        let event = event?;
+        if &event.who == signer.account_id() {
+            reclaimed += event.amount;
+        }
+    }
+    Ok(reclaimed)
+}
+
+/// Call data of pending multisig operations, persisted as one file per call hash.
+///
+/// Only the call hash is on chain until the final approval, so the signatories share a store to
+/// execute the call with `as_multi`.
+#[derive(Clone, Debug)]
+pub struct CallStore {
+    dir: PathBuf,
+}
+
+impl CallStore {
+    /// Opens the store in `dir`, creating it if it does not exist.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, Box<dyn Error>> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+        })
+    }
+
+    fn path(&self, call_hash: &[u8; 32]) -> PathBuf {
+        self.dir.join(format!("0x{}.scale", hex::encode(call_hash)))
+    }
+
+    /// Stores the `call`, returning its hash.
+    pub fn insert(&self, call: &EncodedCall) -> Result<[u8; 32], Box<dyn Error>> {
+        let call_hash = call_hash(call);
+        fs::write(self.path(&call_hash), call.encode())?;
+        Ok(call_hash)
+    }
+
+    pub fn get(&self, call_hash: &[u8; 32]) -> Result<Option<EncodedCall>, Box<dyn Error>> {
+        match fs::read(self.path(call_hash)) {
+            Ok(data) => Ok(Some(EncodedCall::decode(&mut &data[..])?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn remove(&self, call_hash: &[u8; 32]) -> Result<(), Box<dyn Error>> {
+        match fs::remove_file(self.path(call_hash)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Result of [`MultisigCoordinator::approve`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Approval {
+    /// The operation needs more approvals, these signatories have approved so far.
+    Pending { approvals: Vec<AccountId32> },
+    /// The final approval executed the call.
+    Executed,
+}
+
+/// Creates, approves and executes multisig operations for a set of signatories.
+///
+/// Approvals only submit the call hash with `approve_as_multi`, except for the final approval which
+/// executes the call with `as_multi` using the call data from the [`CallStore`].
+#[derive(Clone)]
+pub struct MultisigCoordinator {
+    api: PolkadotRuntimeApi,
+    multisig: MultisigAccount,
+    store: CallStore,
+}
+
+impl MultisigCoordinator {
+    /// Multisigs with a threshold of 1 dispatch directly with `as_multi_threshold_1`, so they are
+    /// rejected.
+    pub fn new(api: PolkadotRuntimeApi, multisig: MultisigAccount, store: CallStore) -> Result<Self, Box<dyn Error>> {
+        if multisig.threshold < 2 {
+            return Err("The coordinator needs a threshold of at least 2".into());
+        }
+        Ok(Self { api, multisig, store })
+    }
+
+    pub fn multisig(&self) -> &MultisigAccount {
+        &self.multisig
+    }
+
+    /// The multisig account.
+    pub fn account(&self) -> AccountId32 {
+        self.multisig.account_id()
+    }
+
+    pub fn store(&self) -> &CallStore {
+        &self.store
+    }
+
+    /// The pending operation for the `call_hash` from `multisig.Multisigs`, including the
+    /// approvals.
+    pub async fn status(&self, call_hash: &[u8; 32]) -> Result<Option<Multisig>, Box<dyn Error>> {
+        Ok(self
+            .api
+            .storage()
+            .multisig()
+            .multisigs(&self.account(), call_hash, None)
+            .await?)
+    }
+
+    /// Stores the `call` and approves it as the first signatory, reserving the deposit from
+    /// [`crate::deposits::multisig_deposit`].
+    ///
+    /// Returns the call hash for the other signatories to approve.
+    pub async fn propose(&self, signer: &PolkadotSigner, call: &EncodedCall) -> Result<[u8; 32], Box<dyn Error>> {
+        let call_hash = self.store.insert(call)?;
+        self.approve(signer, &call_hash).await?;
+        Ok(call_hash)
+    }
+
+    /// Approves the `call_hash` as the `signer`, executing the call if this is the final approval
+    /// and the call data is in the store.
+    ///
+    /// Without the call data the final approval is only recorded, any signatory can execute it later
+    /// once the call data is in the store. The `max_weight` is [`fees::max_weight`] of the stored
+    /// call.
+    pub async fn approve(&self, signer: &PolkadotSigner, call_hash: &[u8; 32]) -> Result<Approval, Box<dyn Error>> {
+        let who = signer.account_id();
+        let other_signatories = other_signatories(&self.api, &self.multisig, who)?;
+        let (maybe_timepoint, mut approvals) = match self.status(call_hash).await? {
+            Some(multisig) => (Some(multisig.when), multisig.approvals),
+            None => (None, Vec::new()),
+        };
+        // signatories which already approved can still execute once there are enough approvals
+        let approved = approvals.contains(who);
+        let is_final = approvals.len() + usize::from(!approved) >= self.multisig.threshold as usize;
+        let call = self.store.get(call_hash)?;
+        // only checked when the call is executed, so without the call data there is nothing to check
+        let max_weight = match &call {
+            Some(call) => fees::max_weight(self.api.clone(), call).await?,
+            None => 0,
+        };
+        if let (true, Some(call)) = (is_final, call) {
+            let as_multi = EncodedCall::Multisig(MultisigCall::as_multi {
+                threshold: self.multisig.threshold,
+                other_signatories,
+                maybe_timepoint,
+                call: WrapperKeepOpaque::from_encoded(call.encode()),
+                store_call: false,
+                max_weight,
+            });
+            let events = tx::sign_and_submit(self.api.clone(), as_multi, signer).await?;
+            let executed = events
+                .find_first::<MultisigExecutedEvent>()?
+                .ok_or("Multisig was not executed")?;
+            self.store.remove(call_hash)?;
+            // the approval succeeds even if the call fails, the result is in the event
+            return match executed.result {
+                Ok(()) => Ok(Approval::Executed),
+                Err(err) => Err(format!("Multisig call failed: {}", describe_dispatch_error(&self.api, &err)).into()),
+            };
+        }
+
+        if approved {
+            return Err(format!("{} already approved", Address::for_chain(&self.api, who.clone())?).into());
+        }
+        let approve_as_multi = EncodedCall::Multisig(MultisigCall::approve_as_multi {
+            threshold: self.multisig.threshold,
+            other_signatories,
+            maybe_timepoint,
+            call_hash: *call_hash,
+            max_weight,
+        });
+        tx::sign_and_submit(self.api.clone(), approve_as_multi, signer).await?;
+        approvals.push(who.clone());
+        Ok(Approval::Pending { approvals })
+    }
+
+    /// Cancels the `call_hash` as its depositor, see [`cancel_multisig`].
+    pub async fn cancel(&self, signer: &PolkadotSigner, call_hash: &[u8; 32]) -> Result<u128, Box<dyn Error>> {
+        let reclaimed = cancel_multisig(self.api.clone(), signer, &self.multisig, *call_hash).await?;
+        self.store.remove(call_hash)?;
+        Ok(reclaimed)
+    }
+
+    /// Cancels the pending operations of the `signer` whose first approval is more than
+    /// `older_than` blocks before the finalized head.
+    pub async fn sweep(&self, signer: &PolkadotSigner, older_than: u32) -> Result<SweepReport, Box<dyn Error>> {
+        let block_hash = accounts::pin_block(&self.api, None).await?;
+        let block_number = accounts::block_number(&self.api, block_hash).await?;
+        let mut cancelled = Vec::new();
+        for pending in pending_multisigs(self.api.clone(), &self.account(), Some(&self.store)).await? {
+            if &pending.depositor != signer.account_id()
+                || block_number.saturating_sub(pending.when.height) <= older_than
+            {
+                continue;
+            }
+            cancelled.push(Cancelled {
+                reclaimed: self.cancel(signer, &pending.call_hash).await?,
+                call_hash: pending.call_hash,
+                height: pending.when.height,
+            });
+        }
+        Ok(SweepReport {
+            block_number,
+            cancelled,
+        })
+    }
+}
+
+/// An operation cancelled by [`MultisigCoordinator::sweep`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cancelled {
+    pub call_hash: [u8; 32],
+    /// Block number of the first approval.
+    pub height: u32,
+    pub reclaimed: u128,
+}
+
+/// Result of [`MultisigCoordinator::sweep`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepReport {
+    /// The finalized head the age of the operations was compared against.
+    pub block_number: u32,
+    pub cancelled: Vec<Cancelled>,
+}
+
+impl SweepReport {
+    /// Total deposit reclaimed over all cancelled operations.
+    pub fn reclaimed(&self) -> u128 {
+        self.cancelled.iter().map(|cancelled| cancelled.reclaimed).sum()
+    }
+}
+
+/// An entry of `multisig.Multisigs`, see [`pending_multisigs`].
+#[derive(Debug)]
+pub struct PendingMultisig {
+    pub call_hash: [u8; 32],
+    /// Block number and extrinsic index of the first approval.
+    pub when: Timepoint,
+    pub deposit: u128,
+    pub depositor: AccountId32,
+    pub approvals: Vec<AccountId32>,
+    /// The call if its data was found, see [`pending_multisigs`].
+    pub call: Option<EncodedCall>,
+}
+
+/// Lists the pending operations of the `multisig_account` at the finalized head.
+///
+/// The call data is looked up in the `store`, in `multisig.Calls` for calls stored with
+/// `store_call`, and in the extrinsic of the first approval if it was an `as_multi`.
+pub async fn pending_multisigs(
+    api: PolkadotRuntimeApi,
+    multisig_account: &AccountId32,
+    store: Option<&CallStore>,
+) -> Result<Vec<PendingMultisig>, Box<dyn Error>> {
+    const PAGE_SIZE: u32 = 100;
+    let block_hash = accounts::pin_block(&api, None).await?;
+    // the first key is hashed with `Twox64Concat`, so all operations of the account share a prefix
+    let account = multisig_account.encode();
+    let prefix = [
+        &twox_128(b"Multisig")[..],
+        &twox_128(b"Multisigs"),
+        &twox_64(&account),
+        &account,
+    ]
+    .concat();
+
+    let mut pending = Vec::new();
+    let mut start_key = None;
+    loop {
+        let keys = api
+            .client
+            .rpc()
+            .storage_keys_paged(Some(StorageKey(prefix.clone())), PAGE_SIZE, start_key, Some(block_hash))
+            .await?;
+        for key in &keys {
+            // the second key is hashed with `Blake2_128Concat` so it ends with the call hash
+            let call_hash: [u8; 32] = key.0[key.0.len() - 32..].try_into()?;
+            let multisig = match api
+                .storage()
+                .multisig()
+                .multisigs(multisig_account, &call_hash, Some(block_hash))
+                .await?
+            {
+                Some(multisig) => multisig,
+                None => continue,
+            };
+            let call = match store.map(|store| store.get(&call_hash)).transpose()?.flatten() {
+                Some(call) => Some(call),
+                None => find_call(&api, &multisig.when, &call_hash, block_hash).await?,
+            };
+            pending.push(PendingMultisig {
+                call_hash,
+                when: multisig.when,
+                deposit: multisig.deposit,
+                depositor: multisig.depositor,
+                approvals: multisig.approvals,
+                call,
+            });
+        }
+        if keys.len() < PAGE_SIZE as usize {
+            break;
+        }
+        start_key = keys.last().cloned();
+    }
+    pending.sort_by_key(|pending| (pending.when.height, pending.when.index));
+    Ok(pending)
+}
+
+/// Looks up the call data on chain, in `multisig.Calls` or in the extrinsic at the `when` timepoint.
+async fn find_call(
+    api: &PolkadotRuntimeApi,
+    when: &Timepoint,
+    call_hash: &[u8; 32],
+    block_hash: H256,
+) -> Result<Option<EncodedCall>, Box<dyn Error>> {
+    if let Some((call, _, _)) = api.storage().multisig().calls(call_hash, Some(block_hash)).await? {
+        return Ok(call.try_decode());
+    }
+
+    let rpc = api.client.rpc();
+    let block = match rpc.block_hash(Some(when.height.into())).await? {
+        Some(hash) => rpc.block(Some(hash)).await?.ok_or("Block not found")?.block,
+        None => return Ok(None),
+    };
+    let extrinsic = match block.extrinsics.get(when.index as usize) {
+        // opaque extrinsics encode with a length prefix
+        Some(extrinsic) => Vec::<u8>::decode(&mut &extrinsic.encode()[..])?,
+        None => return Ok(None),
+    };
+    // extrinsics of other runtime versions may not decode
+    Ok(tx::decode_call(&extrinsic)
+        .ok()
+        .and_then(|call| find_as_multi(call, call_hash)))
+}
+
+/// Finds the call of an `as_multi` with the `call_hash` in the `call`, which may be wrapped in
+/// proxy and utility calls.
+fn find_as_multi(call: EncodedCall, call_hash: &[u8; 32]) -> Option<EncodedCall> {
+    match call {
+        EncodedCall::Multisig(MultisigCall::as_multi { call, .. }) => {
+            if blake2_256(call.encoded()) == *call_hash {
+                call.try_decode()
+            } else {
+                call.try_decode().and_then(|call| find_as_multi(call, call_hash))
+            }
+        }
+        EncodedCall::Multisig(MultisigCall::as_multi_threshold_1 { call, .. })
+        | EncodedCall::Proxy(ProxyCall::proxy { call, .. })
+        | EncodedCall::Proxy(ProxyCall::proxy_announced { call, .. })
+        | EncodedCall::Utility(UtilityCall::as_derivative { call, .. })
+        | EncodedCall::Utility(UtilityCall::dispatch_as { call, .. }) => find_as_multi(*call, call_hash),
+        EncodedCall::Utility(UtilityCall::batch { calls }) | EncodedCall::Utility(UtilityCall::batch_all { calls }) => {
+            calls.into_iter().find_map(|call| find_as_multi(call, call_hash))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{
+    address::{self, Address},
+    balance::Token,
+    fees::FeeEstimator,
+    polkadot,
+    signer::DummySigner,
+    tx::{self, NonceManager},
+    EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
+};
+use futures::future::join_all;
+use std::{collections::VecDeque, error::Error, fmt};
+use subxt::{
+    codec::Encode,
+    sp_core::H256,
+    sp_runtime::{AccountId32, MultiAddress, Perbill},
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+type UtilityCall = polkadot::runtime_types::pallet_utility::pallet::Call;
+
+/// A single payment of `amount` to the destination.
+pub type Recipient = (MultiAddress<AccountId32, ()>, u128);
+
+/// Outcome of paying a single [`Recipient`].
+#[derive(Clone, Debug)]
+pub struct PayoutOutcome {
+    pub dest: MultiAddress<AccountId32, ()>,
+    pub amount: u128,
+    /// Index of the batch the transfer was submitted in.
+    pub chunk: usize,
+    /// The block the transfer was finalized in, or why it failed.
+    pub result: Result<H256, String>,
+}
+
+/// Per-recipient result of [`PayoutEngine::pay`], in the same order as the input rows.
+#[derive(Clone, Debug, Default)]
+pub struct PayoutReport {
+    pub outcomes: Vec<PayoutOutcome>,
+    /// Network prefix of the chain, used to display addresses.
+    pub ss58_prefix: u16,
+    /// Native token of the chain, used to display amounts.
+    pub token: Token,
+}
+
+impl PayoutReport {
+    pub fn succeeded(&self) -> impl Iterator<Item = &PayoutOutcome> {
+        self.outcomes.iter().filter(|outcome| outcome.result.is_ok())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &PayoutOutcome> {
+        self.outcomes.iter().filter(|outcome| outcome.result.is_err())
+    }
+
+    /// Sum of all successful transfers.
+    pub fn total_paid(&self) -> u128 {
+        self.succeeded().map(|outcome| outcome.amount).sum()
+    }
+}
+
+impl fmt::Display for PayoutReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for PayoutOutcome {
+            dest,
+            amount,
+            chunk,
+            result,
+        } in &self.outcomes
+        {
+            let dest = match dest {
+                MultiAddress::Id(account) => Address::new(account.clone(), self.ss58_prefix).to_string(),
+                dest => format!("{dest:?}"),
+            };
+            let result = match result {
+                Ok(block_hash) => format!("finalized in {block_hash:?}"),
+                Err(err) => err.clone(),
+            };
+            let amount = self.token.balance(*amount).to_string();
+            writeln!(f, "{chunk:>5}  {dest:<48}  {amount:>24}  {result}")?;
+        }
+        writeln!(
+            f,
+            "paid: {}, failed: {}",
+            self.token.balance(self.total_paid()),
+            self.failed().count()
+        )
+    }
+}
+
+struct ChunkLimits {
+    base_extrinsic: u64,
+    weight: u64,
+    length: u32,
+    calls: u32,
+}
+
+/// Splits large lists of transfers into `utility.batch_all` calls which fit comfortably in a block.
+#[derive(Clone)]
+pub struct PayoutEngine {
+    api: PolkadotRuntimeApi,
+    weight_fraction: Perbill,
+    length_fraction: Perbill,
+    max_batch_size: Option<u32>,
+}
+
+impl PayoutEngine {
+    /// By default each batch uses at most half of the normal block weight and length.
+    pub fn new(api: PolkadotRuntimeApi) -> Self {
+        Self {
+            api,
+            weight_fraction: Perbill::from_percent(50),
+            length_fraction: Perbill::from_percent(50),
+            max_batch_size: None,
+        }
+    }
+
+    /// Fraction of the maximum normal extrinsic weight each batch may use.
+    pub fn weight_fraction(mut self, fraction: Perbill) -> Self {
+        self.weight_fraction = fraction;
+        self
+    }
+
+    /// Fraction of the maximum normal block length each batch may use.
+    pub fn length_fraction(mut self, fraction: Perbill) -> Self {
+        self.length_fraction = fraction;
+        self
+    }
+
+    /// Limits the number of transfers per batch, in addition to the `BatchedCallsLimit` constant.
+    pub fn max_batch_size(mut self, max_batch_size: u32) -> Self {
+        self.max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    fn limits(&self) -> Result<ChunkLimits, Box<dyn Error>> {
+        let block_weights = self.api.constants().system().block_weights()?;
+        let normal = block_weights.per_class.normal;
+        let max_weight = normal
+            .max_extrinsic
+            .or(normal.max_total)
+            .unwrap_or(block_weights.max_block);
+        let max_length = self.api.constants().system().block_length()?.max.normal;
+        let batched_calls_limit = self.api.constants().utility().batched_calls_limit()?;
+
+        Ok(ChunkLimits {
+            base_extrinsic: normal.base_extrinsic,
+            weight: self.weight_fraction * max_weight,
+            length: self.length_fraction * max_length,
+            calls: self
+                .max_batch_size
+                .map_or(batched_calls_limit, |size| size.min(batched_calls_limit)),
+        })
+    }
+
+    /// Splits the `recipients` into batches that `sender` can submit, without submitting anything.
+    pub async fn plan(
+        &self,
+        sender: AccountId32,
+        recipients: Vec<Recipient>,
+    ) -> Result<Vec<Vec<Recipient>>, Box<dyn Error>> {
+        let first = match recipients.first() {
+            Some(first) => first.clone(),
+            None => return Ok(Vec::new()),
+        };
+        let limits = self.limits()?;
+        let estimator = FeeEstimator::new(self.api.clone());
+        let base_extrinsic = limits.base_extrinsic;
+
+        // `payment_queryInfo` returns the dispatch weight, the base extrinsic weight comes on top
+        let batch_weight = estimator
+            .estimate_for(&batch_all(Vec::new()), sender.clone())
+            .await?
+            .weight;
+        // every row is the same call so estimate once what a transfer adds to the batch
+        let call_weight = estimator
+            .estimate_for(&batch_all(vec![transfer(first)]), sender.clone())
+            .await?
+            .weight
+            .saturating_sub(batch_weight);
+        let overhead_weight = base_extrinsic + batch_weight;
+        // the signature, extensions and `batch_all` wrapper are the same for every chunk
+        let mut dummy_signer = DummySigner::new(sender.clone());
+        dummy_signer.set_nonce(u32::MAX);
+        let overhead_length = tx::create_signed(self.api.clone(), &batch_all(Vec::new()), &dummy_signer, None)
+            .await?
+            .len() as u32;
+        if overhead_weight + call_weight > limits.weight || overhead_length > limits.length {
+            return Err("Block limits are too low for a single transfer".into());
+        }
+
+        let mut chunks = Vec::new();
+        let mut chunk: Vec<Recipient> = Vec::new();
+        let (mut weight, mut length) = (overhead_weight, overhead_length);
+        for recipient in recipients {
+            let recipient_length = transfer(recipient.clone()).encoded_size() as u32;
+            if !chunk.is_empty()
+                && (chunk.len() as u32 >= limits.calls
+                    || weight + call_weight > limits.weight
+                    || length + recipient_length > limits.length)
+            {
+                chunks.push(std::mem::take(&mut chunk));
+                weight = overhead_weight;
+                length = overhead_length;
+            }
+            weight += call_weight;
+            length += recipient_length;
+            chunk.push(recipient);
+        }
+        chunks.push(chunk);
+
+        // confirm with the real estimate of each batch in case the weight of a transfer varies
+        let mut chunks = VecDeque::from(chunks);
+        let mut verified = Vec::new();
+        while let Some(mut chunk) = chunks.pop_front() {
+            let weight = estimator
+                .estimate_for(
+                    &batch_all(chunk.iter().cloned().map(transfer).collect()),
+                    sender.clone(),
+                )
+                .await?
+                .weight;
+            if base_extrinsic + weight > limits.weight && chunk.len() > 1 {
+                let second = chunk.split_off(chunk.len() / 2);
+                chunks.push_front(second);
+                chunks.push_front(chunk);
+            } else {
+                verified.push(chunk);
+            }
+        }
+        Ok(verified)
+    }
+
+    /// Sum of the fees `sender` would pay to submit the planned `chunks`.
+    pub async fn estimate_fees(&self, sender: AccountId32, chunks: &[Vec<Recipient>]) -> Result<u128, Box<dyn Error>> {
+        let estimator = FeeEstimator::new(self.api.clone());
+        let mut fees = 0;
+        for chunk in chunks {
+            let call = batch_all(chunk.iter().cloned().map(transfer).collect());
+            fees += estimator.estimate_for(&call, sender.clone()).await?.total();
+        }
+        Ok(fees)
+    }
+
+    /// Pays all `recipients` from the `signer`, submitting the batches back to back.
+    ///
+    /// If a batch is rejected by the transaction pool the remaining batches are not submitted.
+    pub async fn pay(
+        &self,
+        signer: &PolkadotSigner,
+        recipients: Vec<Recipient>,
+    ) -> Result<PayoutReport, Box<dyn Error>> {
+        let chunks = self.plan(signer.account_id().clone(), recipients).await?;
+        let nonces = NonceManager::new(self.api.clone(), signer.account_id()).await?;
+
+        let mut extrinsics = Vec::new();
+        for chunk in &chunks {
+            let call = batch_all(chunk.iter().cloned().map(transfer).collect());
+            extrinsics.push(tx::create_signed(self.api.clone(), &call, signer, Some(nonces.next())).await?);
+        }
+
+        let mut pending = Vec::new();
+        let mut rejected = None;
+        for extrinsic in extrinsics {
+            match tx::submit(&self.api, extrinsic).await {
+                Ok(progress) => pending.push(progress.wait_for_finalized_success()),
+                Err(err) => {
+                    rejected = Some(err.to_string());
+                    break;
+                }
+            }
+        }
+        let mut results: Vec<Result<H256, String>> = join_all(pending)
+            .await
+            .into_iter()
+            .map(|result| result.map(|events| events.block_hash()).map_err(|err| err.to_string()))
+            .collect();
+        if let Some(err) = rejected {
+            results.push(Err(err));
+        }
+
+        let mut outcomes = Vec::new();
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            // `batch_all` is atomic so every transfer in a batch shares its result
+            let result = results
+                .get(index)
+                .cloned()
+                .unwrap_or_else(|| Err("Not submitted".to_string()));
+            outcomes.extend(chunk.into_iter().map(|(dest, amount)| PayoutOutcome {
+                dest,
+                amount,
+                chunk: index,
+                result: result.clone(),
+            }));
+        }
+        Ok(PayoutReport {
+            outcomes,
+            ss58_prefix: address::chain_prefix(&self.api)?,
+            token: Token::from_chain(&self.api).await?,
+        })
+    }
+}
+
+fn transfer((dest, value): Recipient) -> EncodedCall {
+    EncodedCall::Balances(BalancesCall::transfer { dest, value })
+}
+
+fn batch_all(calls: Vec<EncodedCall>) -> EncodedCall {
+    EncodedCall::Utility(UtilityCall::batch_all { calls })
+}
@@ -0,0 +1,365 @@
+use crate::{
+    address::{self, Address, GENERIC_PREFIX},
+    balance::Token,
+    payout::{PayoutEngine, PayoutReport, Recipient},
+    PolkadotRuntimeApi, PolkadotSigner,
+};
+use serde::Deserialize;
+use std::{error::Error, fmt, fs, path::Path};
+use subxt::sp_runtime::{AccountId32, MultiAddress};
+
+/// A row of a payout file, before it is validated against the chain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutRow {
+    /// Line in the CSV file, or position in the JSON array, starting at 1.
+    pub line: usize,
+    pub address: String,
+    pub amount: String,
+}
+
+/// Reads a `.csv` or `.json` payout file, see [`parse_csv`] and [`parse_json`].
+pub fn read_payout_file(path: impl AsRef<Path>) -> Result<Vec<PayoutRow>, Box<dyn Error>> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => parse_csv(&contents),
+        Some(extension) if extension.eq_ignore_ascii_case("json") => parse_json(&contents),
+        _ => Err(format!("Unsupported payout file: {}", path.display()).into()),
+    }
+}
+
+/// Parses CSV with an `address` and an `amount` column, other columns are ignored.
+pub fn parse_csv(contents: &str) -> Result<Vec<PayoutRow>, Box<dyn Error>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+            .ok_or(format!("Missing `{name}` column"))
+    };
+    let (address, amount) = (column("address")?, column("amount")?);
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |position| position.line() as usize);
+        rows.push(PayoutRow {
+            line,
+            address: record.get(address).unwrap_or_default().to_string(),
+            amount: record.get(amount).unwrap_or_default().to_string(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Parses a JSON array of `{ "address": "..", "amount": ".." }` objects, amounts may also be
+/// numbers.
+pub fn parse_json(contents: &str) -> Result<Vec<PayoutRow>, Box<dyn Error>> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        // `u128` is not supported in untagged enums, larger amounts have to be strings
+        Number(u64),
+        String(String),
+    }
+
+    #[derive(Deserialize)]
+    struct Row {
+        address: String,
+        amount: Amount,
+    }
+
+    let rows: Vec<Row> = serde_json::from_str(contents)?;
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| PayoutRow {
+            line: index + 1,
+            address: row.address,
+            amount: match row.amount {
+                Amount::Number(amount) => amount.to_string(),
+                Amount::String(amount) => amount,
+            },
+        })
+        .collect())
+}
+
+/// Parses an `amount` in plancks, or in tokens if it has a decimal point or ends with the symbol.
+///
+/// For example on Polkadot `"15"` is 15 plancks, but `"1.5"` and `"1.5 DOT"` are
+/// 15_000_000_000 plancks.
+pub fn parse_amount(amount: &str, token: &Token) -> Result<u128, String> {
+    let amount = amount.trim();
+    if amount.chars().all(|c| c.is_ascii_digit() || c == '_') {
+        return amount
+            .replace('_', "")
+            .parse()
+            .map_err(|_| format!("Invalid amount `{amount}`"));
+    }
+    token.parse(amount).map(u128::from)
+}
+
+/// Problem with a single row, these rows are not paid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RowIssue {
+    /// Not an SS58 address, or the checksum is wrong.
+    InvalidAddress(String),
+    /// A valid address for another network, generic substrate addresses are accepted.
+    WrongNetwork {
+        prefix: u16,
+    },
+    InvalidAmount(String),
+    ZeroAmount,
+    /// The recipient would end up below the existential deposit, so the transfer would fail.
+    BelowExistentialDeposit {
+        balance_after: u128,
+    },
+}
+
+impl RowIssue {
+    /// Describes the issue with amounts in the `token`.
+    pub fn describe(&self, token: &Token) -> String {
+        match self {
+            Self::InvalidAddress(err) => err.clone(),
+            Self::WrongNetwork { prefix } => format!("address is for network {prefix}"),
+            Self::InvalidAmount(err) => err.clone(),
+            Self::ZeroAmount => "amount is zero".to_string(),
+            Self::BelowExistentialDeposit { balance_after } => format!(
+                "balance of {} would be below the existential deposit",
+                token.balance(*balance_after)
+            ),
+        }
+    }
+}
+
+/// Problem with the sender, nothing is paid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SenderIssue {
+    InsufficientBalance {
+        required: u128,
+        transferable: u128,
+    },
+    /// The sender would be left with less than the existential deposit and its account removed.
+    WouldBeReaped {
+        remaining: u128,
+    },
+}
+
+impl SenderIssue {
+    /// Describes the issue with amounts in the `token`.
+    pub fn describe(&self, token: &Token) -> String {
+        match self {
+            Self::InsufficientBalance { required, transferable } => format!(
+                "sender needs {} but only {} is transferable",
+                token.balance(*required),
+                token.balance(*transferable)
+            ),
+            Self::WouldBeReaped { remaining } => {
+                format!("sender would be reaped with {} remaining", token.balance(*remaining))
+            }
+        }
+    }
+}
+
+/// A validated [`PayoutRow`].
+#[derive(Clone, Debug)]
+pub struct PreviewRow {
+    pub row: PayoutRow,
+    /// The parsed recipient, even if there are issues with it.
+    pub recipient: Option<Recipient>,
+    pub issues: Vec<RowIssue>,
+}
+
+/// Result of checking a payout file against the chain, see [`PayoutImporter::preview`].
+#[derive(Clone, Debug)]
+pub struct PayoutPreview {
+    pub sender: AccountId32,
+    /// Network prefix of the chain, used to display addresses.
+    pub ss58_prefix: u16,
+    /// Native token of the chain, used to display amounts.
+    pub token: Token,
+    pub rows: Vec<PreviewRow>,
+    pub existential_deposit: u128,
+    /// Free balance of the sender which is not frozen.
+    pub transferable: u128,
+    /// Sum of all valid rows.
+    pub total: u128,
+    /// Estimated fees for paying all valid rows.
+    pub fees: u128,
+    /// Number of batches the valid rows are paid in.
+    pub batches: usize,
+    pub sender_issues: Vec<SenderIssue>,
+}
+
+impl PayoutPreview {
+    /// `true` if every row can be paid.
+    pub fn is_valid(&self) -> bool {
+        self.sender_issues.is_empty() && self.rows.iter().all(|row| row.issues.is_empty())
+    }
+
+    /// The rows without any issues.
+    pub fn recipients(&self) -> Vec<Recipient> {
+        self.rows
+            .iter()
+            .filter(|row| row.issues.is_empty())
+            .filter_map(|row| row.recipient.clone())
+            .collect()
+    }
+}
+
+impl fmt::Display for PayoutPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "sender: {}", Address::new(self.sender.clone(), self.ss58_prefix))?;
+        for PreviewRow { row, recipient, issues } in &self.rows {
+            let status = if issues.is_empty() {
+                "ok".to_string()
+            } else {
+                issues
+                    .iter()
+                    .map(|issue| issue.describe(&self.token))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            // show valid addresses in the format of the chain
+            let (address, amount) = match recipient {
+                Some((MultiAddress::Id(account), amount)) => (
+                    Address::new(account.clone(), self.ss58_prefix).to_string(),
+                    self.token.balance(*amount).to_string(),
+                ),
+                _ => (row.address.clone(), row.amount.clone()),
+            };
+            writeln!(f, "{:>5}  {address:<48}  {amount:>24}  {status}", row.line)?;
+        }
+        writeln!(
+            f,
+            "total: {}, fees: {}, batches: {}, transferable: {}",
+            self.token.balance(self.total),
+            self.token.balance(self.fees),
+            self.batches,
+            self.token.balance(self.transferable)
+        )?;
+        for issue in &self.sender_issues {
+            writeln!(f, "{}", issue.describe(&self.token))?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates payout files against the chain before paying them with a [`PayoutEngine`].
+#[derive(Clone)]
+pub struct PayoutImporter {
+    api: PolkadotRuntimeApi,
+    engine: PayoutEngine,
+}
+
+impl PayoutImporter {
+    pub fn new(api: PolkadotRuntimeApi) -> Self {
+        Self {
+            engine: PayoutEngine::new(api.clone()),
+            api,
+        }
+    }
+
+    /// Uses a custom [`PayoutEngine`] to plan and pay the batches.
+    pub fn engine(mut self, engine: PayoutEngine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Checks the `rows` and the balance of the `sender`, without submitting anything.
+    pub async fn preview(&self, sender: AccountId32, rows: Vec<PayoutRow>) -> Result<PayoutPreview, Box<dyn Error>> {
+        let token = Token::from_chain(&self.api).await?;
+        let ss58_prefix = address::chain_prefix(&self.api)?;
+        let existential_deposit = self.api.constants().balances().existential_deposit()?;
+
+        let mut preview_rows = Vec::new();
+        for row in rows {
+            let mut issues = Vec::new();
+            let account = match address::decode(&row.address) {
+                Ok((account, prefix)) => {
+                    if prefix != ss58_prefix && prefix != GENERIC_PREFIX {
+                        issues.push(RowIssue::WrongNetwork { prefix });
+                    }
+                    Some(account)
+                }
+                Err(err) => {
+                    issues.push(RowIssue::InvalidAddress(err.to_string()));
+                    None
+                }
+            };
+            let amount = match parse_amount(&row.amount, &token) {
+                Ok(0) => {
+                    issues.push(RowIssue::ZeroAmount);
+                    None
+                }
+                Ok(amount) => Some(amount),
+                Err(err) => {
+                    issues.push(RowIssue::InvalidAmount(err));
+                    None
+                }
+            };
+
+            let recipient = match (account, amount) {
+                (Some(account), Some(amount)) => {
+                    let data = self.api.storage().system().account(&account, None).await?.data;
+                    let balance_after = data.free.saturating_add(data.reserved).saturating_add(amount);
+                    if balance_after < existential_deposit {
+                        issues.push(RowIssue::BelowExistentialDeposit { balance_after });
+                    }
+                    Some((account.into(), amount))
+                }
+                _ => None,
+            };
+            preview_rows.push(PreviewRow { row, recipient, issues });
+        }
+
+        let mut preview = PayoutPreview {
+            sender: sender.clone(),
+            ss58_prefix,
+            token: token.clone(),
+            rows: preview_rows,
+            existential_deposit,
+            transferable: 0,
+            total: 0,
+            fees: 0,
+            batches: 0,
+            sender_issues: Vec::new(),
+        };
+        let recipients = preview.recipients();
+        preview.total = recipients.iter().map(|(_, amount)| amount).sum();
+        if !recipients.is_empty() {
+            let chunks = self.engine.plan(sender.clone(), recipients).await?;
+            preview.fees = self.engine.estimate_fees(sender.clone(), &chunks).await?;
+            preview.batches = chunks.len();
+        }
+
+        let data = self.api.storage().system().account(&sender, None).await?.data;
+        preview.transferable = data.free.saturating_sub(data.misc_frozen.max(data.fee_frozen));
+        let required = preview.total + preview.fees;
+        if required > preview.transferable {
+            preview.sender_issues.push(SenderIssue::InsufficientBalance {
+                required,
+                transferable: preview.transferable,
+            });
+        } else if data.free - required < existential_deposit {
+            preview.sender_issues.push(SenderIssue::WouldBeReaped {
+                remaining: data.free - required,
+            });
+        }
+        Ok(preview)
+    }
+
+    /// Pays a [`PayoutPreview`] from the `signer`, only if it has no issues.
+    pub async fn pay(&self, signer: &PolkadotSigner, preview: &PayoutPreview) -> Result<PayoutReport, Box<dyn Error>> {
+        if signer.account_id() != &preview.sender {
+            return Err("The preview was created for another sender".into());
+        }
+        if !preview.is_valid() {
+            return Err("The preview has issues, fix the payout file first".into());
+        }
+        self.engine.pay(signer, preview.recipients()).await
+    }
+}
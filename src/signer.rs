@@ -0,0 +1,204 @@
+use codec::{Decode, Encode};
+use std::{
+    error::Error,
+    io::{Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::Path,
+    sync::Mutex,
+    time::Duration,
+};
+use subxt::{
+    extrinsic::Signer,
+    sp_core::{ecdsa, ed25519, sr25519},
+    sp_runtime::{AccountId32, MultiSignature},
+    DefaultConfig, PairSigner,
+};
+
+/// Signs with an `sr25519` key, as used by the dev accounts in `sp_keyring`.
+pub type Sr25519Signer = PairSigner<DefaultConfig, sr25519::Pair>;
+
+/// Signs with an `ed25519` key.
+pub type Ed25519Signer = PairSigner<DefaultConfig, ed25519::Pair>;
+
+/// Signs with an `ecdsa` (secp256k1) key.
+pub type EcdsaSigner = PairSigner<DefaultConfig, ecdsa::Pair>;
+
+/// Message sent by the [`RemoteSigner`] to the signing process.
+#[derive(Clone, Debug, Encode, Decode)]
+pub enum SignerRequest {
+    /// Ask for the account the remote key signs for.
+    AccountId,
+    /// Ask for a signature over the signer payload.
+    Sign(Vec<u8>),
+}
+
+/// Reply from the signing process to a [`SignerRequest`].
+#[derive(Clone, Debug, Encode, Decode)]
+pub enum SignerResponse {
+    AccountId(AccountId32),
+    Signature(MultiSignature),
+    Error(String),
+}
+
+/// Upper bound for the length of a message, signer payloads over 256 bytes are hashed before
+/// signing so real messages are far smaller.
+const MAX_MESSAGE_LEN: usize = 4096;
+
+/// How long the [`RemoteSigner`] waits for the signing process to accept or answer a request.
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Writes a length-prefixed SCALE message to the socket.
+fn write_message<T: Encode>(stream: &mut UnixStream, message: &T) -> std::io::Result<()> {
+    let bytes = message.encode();
+    if bytes.len() > MAX_MESSAGE_LEN {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Message of {} bytes is too long", bytes.len()),
+        ));
+    }
+    stream.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    stream.write_all(&bytes)?;
+    stream.flush()
+}
+
+/// Reads a length-prefixed SCALE message from the socket, rejecting messages longer than
+/// [`MAX_MESSAGE_LEN`] before allocating.
+fn read_message<T: Decode>(stream: &mut UnixStream) -> Result<T, Box<dyn Error>> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_MESSAGE_LEN {
+        return Err(format!("Message of {len} bytes is too long").into());
+    }
+    let mut bytes = vec![0u8; len];
+    stream.read_exact(&mut bytes)?;
+    Ok(T::decode(&mut &bytes[..])?)
+}
+
+/// A [`Signer`] which forwards signing requests over a unix socket to a separate process,
+/// so that the key never has to be loaded by the process submitting extrinsics.
+///
+/// The other end of the socket can be served with [`serve_remote_signer`].
+pub struct RemoteSigner {
+    account_id: AccountId32,
+    nonce: Option<u32>,
+    stream: Mutex<UnixStream>,
+}
+
+impl RemoteSigner {
+    /// Connects to the signing process and asks it for the account it signs for.
+    ///
+    /// Signing blocks the calling thread, so every request times out after 30 seconds instead of
+    /// stalling the async runtime on an unresponsive signing process.
+    pub fn connect<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let mut stream = UnixStream::connect(path)?;
+        stream.set_read_timeout(Some(REMOTE_SIGNER_TIMEOUT))?;
+        stream.set_write_timeout(Some(REMOTE_SIGNER_TIMEOUT))?;
+        write_message(&mut stream, &SignerRequest::AccountId)?;
+        match read_message(&mut stream)? {
+            SignerResponse::AccountId(account_id) => Ok(Self {
+                account_id,
+                nonce: None,
+                stream: Mutex::new(stream),
+            }),
+            SignerResponse::Error(err) => Err(err.into()),
+            response => Err(format!("Unexpected response: {response:?}").into()),
+        }
+    }
+
+    /// Sets the nonce to a new value, otherwise it is fetched from the node.
+    pub fn set_nonce(&mut self, nonce: u32) {
+        self.nonce = Some(nonce);
+    }
+
+    fn request_signature(&self, signer_payload: &[u8]) -> Result<MultiSignature, Box<dyn Error>> {
+        let mut stream = self.stream.lock().map_err(|_| "Remote signer connection poisoned")?;
+        write_message(&mut stream, &SignerRequest::Sign(signer_payload.to_vec()))?;
+        match read_message(&mut stream)? {
+            SignerResponse::Signature(signature) => Ok(signature),
+            SignerResponse::Error(err) => Err(err.into()),
+            response => Err(format!("Unexpected response: {response:?}").into()),
+        }
+    }
+}
+
+impl Signer<DefaultConfig> for RemoteSigner {
+    fn nonce(&self) -> Option<u32> {
+        self.nonce
+    }
+
+    fn account_id(&self) -> &AccountId32 {
+        &self.account_id
+    }
+
+    fn address(&self) -> <DefaultConfig as subxt::Config>::Address {
+        self.account_id.clone().into()
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> MultiSignature {
+        // `Signer::sign` is infallible so there is no way to report the failure to the caller
+        self.request_signature(signer_payload)
+            .unwrap_or_else(|err| panic!("Remote signer failed: {err}"))
+    }
+}
+
+/// Answers [`RemoteSigner`] requests on the `listener` using the local `signer`.
+///
+/// Connections are handled one at a time, this returns when the listener fails.
+pub fn serve_remote_signer<S: Signer<DefaultConfig>>(listener: UnixListener, signer: S) -> Result<(), Box<dyn Error>> {
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        // a disconnect or malformed request only ends that connection
+        while let Ok(request) = read_message::<SignerRequest>(&mut stream) {
+            let response = match request {
+                SignerRequest::AccountId => SignerResponse::AccountId(signer.account_id().clone()),
+                SignerRequest::Sign(payload) => SignerResponse::Signature(signer.sign(&payload)),
+            };
+            if write_message(&mut stream, &response).is_err() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A [`Signer`] which produces an invalid `sr25519` signature of the correct length.
+///
+/// Extrinsics signed by this can be used to query fees and weights but are rejected by the node.
+#[derive(Clone, Debug)]
+pub struct DummySigner {
+    account_id: AccountId32,
+    nonce: Option<u32>,
+}
+
+impl DummySigner {
+    pub fn new(account_id: AccountId32) -> Self {
+        Self {
+            account_id,
+            nonce: None,
+        }
+    }
+
+    /// Sets the nonce to a new value, otherwise it is fetched from the node.
+    pub fn set_nonce(&mut self, nonce: u32) {
+        self.nonce = Some(nonce);
+    }
+}
+
+impl Signer<DefaultConfig> for DummySigner {
+    fn nonce(&self) -> Option<u32> {
+        self.nonce
+    }
+
+    fn account_id(&self) -> &AccountId32 {
+        &self.account_id
+    }
+
+    fn address(&self) -> <DefaultConfig as subxt::Config>::Address {
+        self.account_id.clone().into()
+    }
+
+    fn sign(&self, _signer_payload: &[u8]) -> MultiSignature {
+        MultiSignature::Sr25519(sr25519::Signature::from_raw([0u8; 64]))
+    }
+}
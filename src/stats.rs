@@ -0,0 +1,157 @@
+use crate::{
+    accounts::{self, AccountInfo},
+    address::{self, Address},
+    balance::Token,
+    PolkadotRuntimeApi,
+};
+use serde::{Serialize, Serializer};
+use std::{cmp::Reverse, error::Error};
+use subxt::{sp_core::H256, sp_runtime::AccountId32};
+
+/// Percentiles included in every [`Distribution`].
+pub const PERCENTILES: [u8; 7] = [10, 25, 50, 75, 90, 99, 100];
+
+/// Balances are serialized as decimal strings (to not overflow in JavaScript).
+fn serialize_balance<S: Serializer>(balance: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&balance.to_string())
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedAccount {
+    pub address: String,
+    #[serde(serialize_with = "serialize_balance")]
+    pub balance: u128,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Percentile {
+    pub percentile: u8,
+    #[serde(serialize_with = "serialize_balance")]
+    pub balance: u128,
+}
+
+/// Statistics over the non-zero balances of one kind, e.g. all free balances.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Distribution {
+    /// Number of accounts with a non-zero balance.
+    pub holders: usize,
+    #[serde(serialize_with = "serialize_balance")]
+    pub total: u128,
+    pub percentiles: Vec<Percentile>,
+    /// 0 if every holder has the same balance, approaching 1 if one holder has everything.
+    pub gini: f64,
+    /// Largest holders, in descending order.
+    pub top: Vec<RankedAccount>,
+}
+
+impl Distribution {
+    /// Computes the statistics over the non-zero `balances`, formatting the `top_n` addresses with
+    /// `ss58_prefix`.
+    pub fn new(balances: Vec<(AccountId32, u128)>, top_n: usize, ss58_prefix: u16) -> Self {
+        let mut balances: Vec<_> = balances.into_iter().filter(|(_, balance)| *balance > 0).collect();
+        balances.sort_by_key(|(_, balance)| Reverse(*balance));
+        let ascending: Vec<u128> = balances.iter().rev().map(|(_, balance)| *balance).collect();
+
+        Self {
+            holders: balances.len(),
+            total: ascending.iter().sum(),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&percentile| Percentile {
+                    percentile,
+                    balance: nearest_rank(&ascending, percentile),
+                })
+                .collect(),
+            gini: gini(&ascending),
+            top: balances
+                .into_iter()
+                .take(top_n)
+                .map(|(account, balance)| RankedAccount {
+                    address: Address::new(account, ss58_prefix).to_string(),
+                    balance,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The smallest balance such that `percentile`% of the `ascending` balances are less or equal.
+pub fn nearest_rank(ascending: &[u128], percentile: u8) -> u128 {
+    if ascending.is_empty() {
+        return 0;
+    }
+    let rank = (percentile as usize * ascending.len()).div_ceil(100).max(1);
+    ascending[rank.min(ascending.len()) - 1]
+}
+
+/// Gini coefficient of the `ascending` balances.
+///
+/// Source: https://en.wikipedia.org/wiki/Gini_coefficient#Alternative_expressions
+pub fn gini(ascending: &[u128]) -> f64 {
+    let n = ascending.len() as f64;
+    let total: f64 = ascending.iter().map(|&balance| balance as f64).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let weighted: f64 = ascending
+        .iter()
+        .enumerate()
+        .map(|(index, &balance)| (index + 1) as f64 * balance as f64)
+        .sum();
+    2.0 * weighted / (n * total) - (n + 1.0) / n
+}
+
+/// Distribution of free, reserved and frozen balances over all accounts at a block.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DistributionReport {
+    pub block_hash: H256,
+    pub block_number: u32,
+    pub token: Token,
+    pub accounts: usize,
+    pub free: Distribution,
+    pub reserved: Distribution,
+    /// The larger of `misc_frozen` and `fee_frozen`.
+    pub frozen: Distribution,
+}
+
+impl DistributionReport {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Computes the [`DistributionReport`] over every `system.account` at `at` (or the finalized head),
+/// including the `top_n` accounts of each kind.
+pub async fn get_distribution(
+    api: PolkadotRuntimeApi,
+    at: Option<H256>,
+    top_n: usize,
+) -> Result<DistributionReport, Box<dyn Error>> {
+    let block_hash = accounts::pin_block(&api, at).await?;
+    let all_accounts = accounts::get_all_accounts(&api, block_hash).await?;
+    let ss58_prefix = address::chain_prefix(&api)?;
+
+    let balances = |balance: fn(&AccountInfo) -> u128| {
+        all_accounts
+            .iter()
+            .map(|(account, info)| (account.clone(), balance(info)))
+            .collect()
+    };
+    Ok(DistributionReport {
+        block_hash,
+        block_number: accounts::block_number(&api, block_hash).await?,
+        token: Token::from_chain(&api).await?,
+        accounts: all_accounts.len(),
+        free: Distribution::new(balances(|info| info.data.free), top_n, ss58_prefix),
+        reserved: Distribution::new(balances(|info| info.data.reserved), top_n, ss58_prefix),
+        frozen: Distribution::new(
+            balances(|info| info.data.misc_frozen.max(info.data.fee_frozen)),
+            top_n,
+            ss58_prefix,
+        ),
+    })
+}
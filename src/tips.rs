@@ -0,0 +1,216 @@
+use crate::{
+    accounts,
+    address::{self, Address},
+    balance::Token,
+    polkadot, treasury, tx, EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
+};
+use std::{error::Error, fmt};
+use subxt::{
+    sp_core::H256,
+    sp_runtime::{AccountId32, Percent},
+};
+
+type TipsCall = polkadot::runtime_types::pallet_tips::pallet::Call;
+type NewTipEvent = polkadot::tips::events::NewTip;
+type TipClosingEvent = polkadot::tips::events::TipClosing;
+
+/// An entry of `tips.Tips` with its reason and the payout if it closed now.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpenTip {
+    pub hash: H256,
+    /// From `tips.Reasons`, lossy if it is not UTF-8.
+    pub reason: String,
+    /// Receives the tip.
+    pub who: AccountId32,
+    /// Reported the tip, the same as `who` for `tip_new`.
+    pub finder: AccountId32,
+    pub deposit: u128,
+    /// Tips collected so far by tipper.
+    pub tips: Vec<(AccountId32, u128)>,
+    /// Block from which the tip can be closed, `None` until enough tippers have tipped.
+    pub closes: Option<u32>,
+    /// The median of the collected tips, capped at the treasury pot.
+    pub median: u128,
+    /// Part of the median paid to the finder, only for `report_awesome` tips for someone else.
+    pub finders_fee: u128,
+}
+
+impl OpenTip {
+    /// The median minus the finder's fee.
+    pub fn payout(&self) -> u128 {
+        self.median - self.finders_fee
+    }
+}
+
+/// Open tips at a block, see [`get_tips`].
+#[derive(Clone, Debug)]
+pub struct TipsOverview {
+    pub block_hash: H256,
+    pub block_number: u32,
+    pub token: Token,
+    pub ss58_prefix: u16,
+    /// Number of tippers, the members of `phragmen_election`.
+    pub tippers: usize,
+    /// Number of tips after which the countdown starts.
+    pub threshold: usize,
+    /// The `TipCountdown` constant, blocks between reaching the threshold and closing.
+    pub countdown: u32,
+    pub tips: Vec<OpenTip>,
+}
+
+impl fmt::Display for TipsOverview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = &self.token;
+        let address = |account: &AccountId32| Address::new(account.clone(), self.ss58_prefix);
+        writeln!(f, "block: #{} ({:?})", self.block_number, self.block_hash)?;
+        writeln!(
+            f,
+            "tippers: {}, threshold: {}, countdown: {} blocks",
+            self.tippers, self.threshold, self.countdown
+        )?;
+        for tip in &self.tips {
+            writeln!(f, "{:?}  {}", tip.hash, tip.reason)?;
+            writeln!(
+                f,
+                "    who: {}, finder: {}, deposit: {}",
+                address(&tip.who),
+                address(&tip.finder),
+                token.balance(tip.deposit)
+            )?;
+            let closes = match tip.closes {
+                Some(closes) => format!("closes at #{closes}"),
+                None => format!("{} of {} tips", tip.tips.len(), self.threshold),
+            };
+            writeln!(
+                f,
+                "    median: {}, payout: {}, {closes}",
+                token.balance(tip.median),
+                token.balance(tip.payout())
+            )?;
+            for (tipper, value) in &tip.tips {
+                writeln!(
+                    f,
+                    "    {:<48}  {:>24}",
+                    address(tipper).to_string(),
+                    token.balance(*value).to_string()
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the open tips at `at` (or the finalized head) and computes what each would pay out if it
+/// was closed now.
+///
+/// Source: https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/frame/tips/src/lib.rs
+pub async fn get_tips(api: PolkadotRuntimeApi, at: Option<H256>) -> Result<TipsOverview, Box<dyn Error>> {
+    let block_hash = accounts::pin_block(&api, at).await?;
+    let tippers = api.storage().phragmen_election().members(Some(block_hash)).await?.len();
+    let pot = treasury::pot(&api, block_hash).await?;
+    let finders_fee = Percent::from_percent(api.constants().tips().tip_finders_fee()?.0);
+
+    let mut tips = Vec::new();
+    let mut iter = api.storage().tips().tips_iter(Some(block_hash)).await?;
+    while let Some((key, tip)) = iter.next().await? {
+        // the key is hashed with `Twox64Concat` so it ends with the tip hash
+        let hash = H256::from_slice(&key.0[key.0.len() - 32..]);
+        let reason = api
+            .storage()
+            .tips()
+            .reasons(&tip.reason, Some(block_hash))
+            .await?
+            .map(|reason| String::from_utf8_lossy(&reason).to_string())
+            .unwrap_or_default();
+
+        // the payout is the median of the tips, the upper one for an even number of tips
+        let mut values: Vec<u128> = tip.tips.iter().map(|(_, value)| *value).collect();
+        values.sort_unstable();
+        let median = values.get(values.len() / 2).copied().unwrap_or_default().min(pot);
+
+        tips.push(OpenTip {
+            hash,
+            reason,
+            finders_fee: if tip.finders_fee && tip.finder != tip.who {
+                finders_fee * median
+            } else {
+                0
+            },
+            who: tip.who,
+            finder: tip.finder,
+            deposit: tip.deposit,
+            tips: tip.tips,
+            closes: tip.closes,
+            median,
+        });
+    }
+    tips.sort_by_key(|tip| tip.closes.unwrap_or(u32::MAX));
+
+    Ok(TipsOverview {
+        block_hash,
+        block_number: accounts::block_number(&api, block_hash).await?,
+        token: Token::from_chain(&api).await?,
+        ss58_prefix: address::chain_prefix(&api)?,
+        tippers,
+        threshold: tippers.div_ceil(2),
+        countdown: api.constants().tips().tip_countdown()?,
+        tips,
+    })
+}
+
+/// Reports a tip for `who` with a `reason`, reserving the deposit from
+/// [`crate::deposits::tip_report_deposit`].
+///
+/// Returns the tip hash.
+pub async fn report_awesome(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    reason: &str,
+    who: AccountId32,
+) -> Result<H256, Box<dyn Error>> {
+    let call = EncodedCall::Tips(TipsCall::report_awesome {
+        reason: reason.as_bytes().to_vec(),
+        who,
+    });
+    let events = tx::sign_and_submit(api, call, signer).await?;
+    Ok(events
+        .find_first::<NewTipEvent>()?
+        .ok_or("Tip was not opened")?
+        .tip_hash)
+}
+
+/// Opens a tip for `who` with the first `tip_value`, the `signer` has to be a tipper.
+///
+/// Returns the tip hash.
+pub async fn tip_new(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    reason: &str,
+    who: AccountId32,
+    tip_value: u128,
+) -> Result<H256, Box<dyn Error>> {
+    let call = EncodedCall::Tips(TipsCall::tip_new {
+        reason: reason.as_bytes().to_vec(),
+        who,
+        tip_value,
+    });
+    let events = tx::sign_and_submit(api, call, signer).await?;
+    Ok(events
+        .find_first::<NewTipEvent>()?
+        .ok_or("Tip was not opened")?
+        .tip_hash)
+}
+
+/// Tips `tip_value` on an open tip, the `signer` has to be a tipper.
+///
+/// Returns `true` if this tip reached the threshold and started the countdown.
+pub async fn tip(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    hash: H256,
+    tip_value: u128,
+) -> Result<bool, Box<dyn Error>> {
+    let call = EncodedCall::Tips(TipsCall::tip { hash, tip_value });
+    let events = tx::sign_and_submit(api, call, signer).await?;
+    Ok(events.find_first::<TipClosingEvent>()?.is_some())
+}
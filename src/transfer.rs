@@ -0,0 +1,133 @@
+use crate::{
+    address::Address,
+    balance::{Balance, Token},
+    dry_run::describe_dispatch_error,
+    fees::FeeEstimator,
+    polkadot,
+    tx::{self, TxEvents},
+    EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
+};
+use std::error::Error;
+use subxt::{
+    codec::Decode,
+    sp_runtime::{AccountId32, DispatchError, MultiAddress},
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+type TransferEvent = polkadot::balances::events::Transfer;
+
+/// A decoded `balances.Transfer` event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transfer {
+    pub from: AccountId32,
+    pub to: AccountId32,
+    pub amount: Balance,
+}
+
+async fn transfer_event(api: &PolkadotRuntimeApi, events: &TxEvents) -> Result<Transfer, Box<dyn Error>> {
+    // nothing is emitted for zero amounts, e.g. `transfer_all` without any transferable balance
+    let TransferEvent { from, to, amount } = events
+        .find_first::<TransferEvent>()?
+        .ok_or("No `Transfer` event, nothing was transferred")?;
+    let token = Token::from_chain(api).await?;
+    Ok(Transfer {
+        from,
+        to,
+        amount: token.balance(amount),
+    })
+}
+
+/// Fails if `account` would end up below the existential deposit after paying `amount`.
+async fn ensure_not_reaped(
+    api: &PolkadotRuntimeApi,
+    account: &AccountId32,
+    amount: u128,
+) -> Result<(), Box<dyn Error>> {
+    let existential_deposit = api.constants().balances().existential_deposit()?;
+    let free = api.storage().system().account(account, None).await?.data.free;
+    if free.saturating_sub(amount) < existential_deposit {
+        let token = Token::from_chain(api).await?;
+        return Err(format!(
+            "Sending {} would leave {} with less than the existential deposit of {}",
+            token.balance(amount),
+            Address::for_chain(api, account.clone())?,
+            token.balance(existential_deposit)
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Transfers `value` from the `signer` to `dest` using `transfer_keep_alive`.
+///
+/// Fails without submitting if the signer would be left below the existential deposit after fees.
+pub async fn transfer_keep_alive(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    dest: MultiAddress<AccountId32, ()>,
+    value: u128,
+) -> Result<Transfer, Box<dyn Error>> {
+    let call = EncodedCall::Balances(BalancesCall::transfer_keep_alive { dest, value });
+    let fee = FeeEstimator::new(api.clone())
+        .estimate(&call, Some(signer))
+        .await?
+        .total();
+    ensure_not_reaped(&api, signer.account_id(), value.saturating_add(fee)).await?;
+
+    let events = tx::sign_and_submit(api.clone(), call, signer).await?;
+    transfer_event(&api, &events).await
+}
+
+/// Transfers the entire transferable balance of the `signer` to `dest`.
+///
+/// With `keep_alive` the existential deposit is kept, otherwise the signer's account may be reaped.
+pub async fn transfer_all(
+    api: PolkadotRuntimeApi,
+    signer: &PolkadotSigner,
+    dest: MultiAddress<AccountId32, ()>,
+    keep_alive: bool,
+) -> Result<Transfer, Box<dyn Error>> {
+    let call = EncodedCall::Balances(BalancesCall::transfer_all { dest, keep_alive });
+    let events = tx::sign_and_submit(api.clone(), call, signer).await?;
+    transfer_event(&api, &events).await
+}
+
+/// The `force_transfer` call, which needs a root origin, e.g. to propose through governance.
+pub fn force_transfer_call(
+    source: MultiAddress<AccountId32, ()>,
+    dest: MultiAddress<AccountId32, ()>,
+    value: u128,
+) -> EncodedCall {
+    EncodedCall::Balances(BalancesCall::force_transfer { source, dest, value })
+}
+
+/// Transfers `value` from `source` to `dest` with `force_transfer`, dispatched through `sudo.sudo`
+/// by the `sudo_signer`.
+///
+/// Polkadot has no `Sudo` pallet so this only works on test chains which do, use
+/// [`force_transfer_call`] with governance otherwise. Fails without submitting if `source` would
+/// be left below the existential deposit.
+pub async fn force_transfer(
+    api: PolkadotRuntimeApi,
+    sudo_signer: &PolkadotSigner,
+    source: AccountId32,
+    dest: MultiAddress<AccountId32, ()>,
+    value: u128,
+) -> Result<Transfer, Box<dyn Error>> {
+    ensure_not_reaped(&api, &source, value).await?;
+    let call = force_transfer_call(source.into(), dest, value);
+    let sudo_call = tx::encode_dynamic_call(&api, "Sudo", "sudo", &call)?;
+    let extrinsic = tx::create_signed(api.clone(), &sudo_call, sudo_signer, None).await?;
+    let events = tx::submit_and_watch(api.clone(), extrinsic).await?;
+
+    // the extrinsic succeeds even if the call fails, the result is in the `Sudid` event
+    for event in events.iter_raw() {
+        let event = event?;
+        if event.pallet == "Sudo" && event.variant == "Sudid" {
+            if let Err(err) = Result::<(), DispatchError>::decode(&mut &event.data[..])? {
+                return Err(format!("Sudo call failed: {}", describe_dispatch_error(&api, &err)).into());
+            }
+        }
+    }
+    transfer_event(&api, &events).await
+}
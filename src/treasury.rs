@@ -0,0 +1,178 @@
+use crate::{
+    accounts,
+    address::{self, Address},
+    balance::Token,
+    PolkadotRuntimeApi,
+};
+use std::{error::Error, fmt, time::Duration};
+use subxt::{
+    sp_core::H256,
+    sp_runtime::{AccountId32, Permill},
+};
+
+/// An entry of `treasury.Proposals`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryProposal {
+    pub index: u32,
+    pub proposer: AccountId32,
+    pub beneficiary: AccountId32,
+    pub value: u128,
+    pub bond: u128,
+    /// `true` if the proposal is in `treasury.Approvals` and waits for the next spend.
+    pub approved: bool,
+}
+
+/// State of the treasury at a block, see [`get_treasury`].
+#[derive(Clone, Debug)]
+pub struct TreasuryOverview {
+    pub block_hash: H256,
+    pub block_number: u32,
+    pub token: Token,
+    pub ss58_prefix: u16,
+    /// The treasury account derived from the `PalletId` constant.
+    pub account: AccountId32,
+    /// Spendable balance, the free balance of the account minus the existential deposit.
+    pub pot: u128,
+    pub spend_period: u32,
+    pub burn: Permill,
+    pub proposals: Vec<TreasuryProposal>,
+    /// Approved proposal indices, in the order they are paid.
+    pub approvals: Vec<u32>,
+    pub next_spend: u32,
+    /// Estimated from the expected block time.
+    pub next_spend_eta: Duration,
+    /// Approved proposals which the pot can pay at the next spend.
+    pub expected_spend: u128,
+    /// Burned from the remaining pot at the next spend.
+    pub expected_burn: u128,
+}
+
+impl fmt::Display for TreasuryOverview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = &self.token;
+        let address = |account: &AccountId32| Address::new(account.clone(), self.ss58_prefix);
+        writeln!(f, "block: #{} ({:?})", self.block_number, self.block_hash)?;
+        writeln!(f, "treasury: {}", address(&self.account))?;
+        writeln!(f, "pot: {}", token.balance(self.pot))?;
+        writeln!(
+            f,
+            "next spend: #{} in {}s (every {} blocks)",
+            self.next_spend,
+            self.next_spend_eta.as_secs(),
+            self.spend_period
+        )?;
+        writeln!(f, "expected spend: {}", token.balance(self.expected_spend))?;
+        writeln!(
+            f,
+            "expected burn: {} ({:?})",
+            token.balance(self.expected_burn),
+            self.burn
+        )?;
+        for proposal in &self.proposals {
+            writeln!(
+                f,
+                "{:>5}  {} -> {}  value: {}, bond: {}{}",
+                proposal.index,
+                address(&proposal.proposer),
+                address(&proposal.beneficiary),
+                token.balance(proposal.value),
+                token.balance(proposal.bond),
+                if proposal.approved { ", approved" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Spendable balance of the treasury, the free balance of its account minus the existential deposit.
+pub(crate) async fn pot(api: &PolkadotRuntimeApi, block_hash: H256) -> Result<u128, Box<dyn Error>> {
+    let account = accounts::pallet_account(api.constants().treasury().pallet_id()?.0);
+    let existential_deposit = api.constants().balances().existential_deposit()?;
+    let free = api
+        .storage()
+        .system()
+        .account(&account, Some(block_hash))
+        .await?
+        .data
+        .free;
+    Ok(free.saturating_sub(existential_deposit))
+}
+
+/// Predicts the spent and burned amounts of the next spend from the `pot`.
+///
+/// Approved proposals are paid in order if the pot can afford them, then the `burn` fraction of the
+/// rest is burned, unless a proposal was too large and stays approved for the next spend.
+///
+/// Source: https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/frame/treasury/src/lib.rs
+pub fn predict_spend(pot: u128, burn: Permill, approvals: &[u32], proposals: &[TreasuryProposal]) -> (u128, u128) {
+    let mut remaining = pot;
+    let mut missed_any = false;
+    for index in approvals {
+        if let Some(proposal) = proposals.iter().find(|proposal| proposal.index == *index) {
+            if proposal.value <= remaining {
+                remaining -= proposal.value;
+            } else {
+                missed_any = true;
+            }
+        }
+    }
+    let expected_burn = if missed_any { 0 } else { burn * remaining };
+    (pot - remaining, expected_burn)
+}
+
+/// Reads the proposals, approvals and pot of the treasury at `at` (or the finalized head) and
+/// predicts the next spend.
+///
+/// See [`predict_spend`], bounties spend from the pot at the same time, which is not included.
+///
+/// Source: https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/frame/treasury/src/lib.rs
+pub async fn get_treasury(api: PolkadotRuntimeApi, at: Option<H256>) -> Result<TreasuryOverview, Box<dyn Error>> {
+    let block_hash = accounts::pin_block(&api, at).await?;
+    let block_number = accounts::block_number(&api, block_hash).await?;
+    let spend_period = api.constants().treasury().spend_period()?;
+    let burn = api.constants().treasury().burn()?;
+    let account = accounts::pallet_account(api.constants().treasury().pallet_id()?.0);
+
+    let pot = pot(&api, block_hash).await?;
+
+    let approvals = api.storage().treasury().approvals(Some(block_hash)).await?.0;
+    let mut proposals = Vec::new();
+    let mut iter = api.storage().treasury().proposals_iter(Some(block_hash)).await?;
+    while let Some((key, proposal)) = iter.next().await? {
+        // the key is hashed with `Twox64Concat` so it ends with the index
+        let index = u32::from_le_bytes(key.0[key.0.len() - 4..].try_into()?);
+        proposals.push(TreasuryProposal {
+            index,
+            proposer: proposal.proposer,
+            beneficiary: proposal.beneficiary,
+            value: proposal.value,
+            bond: proposal.bond,
+            approved: approvals.contains(&index),
+        });
+    }
+    proposals.sort_by_key(|proposal| proposal.index);
+
+    // spends happen in `on_initialize` of blocks which are a multiple of the period
+    let next_spend = (block_number / spend_period + 1) * spend_period;
+    let block_time = api.constants().babe().expected_block_time()?;
+    let next_spend_eta = Duration::from_millis(block_time * (next_spend - block_number) as u64);
+
+    let (expected_spend, expected_burn) = predict_spend(pot, burn, &approvals, &proposals);
+
+    Ok(TreasuryOverview {
+        block_hash,
+        block_number,
+        token: Token::from_chain(&api).await?,
+        ss58_prefix: address::chain_prefix(&api)?,
+        account,
+        pot,
+        spend_period,
+        burn,
+        proposals,
+        approvals,
+        next_spend,
+        next_spend_eta,
+        expected_spend,
+        expected_burn,
+    })
+}
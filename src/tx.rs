@@ -0,0 +1,186 @@
+use crate::{polkadot, EncodedCall, PolkadotRuntimeApi, PolkadotSigner};
+use scale_info::TypeDef;
+use std::{
+    error::Error,
+    sync::atomic::{AtomicU32, Ordering},
+};
+use subxt::{
+    codec::{Compact, Decode, Encode},
+    extrinsic::ExtrinsicParams,
+    sp_core::blake2_256,
+    sp_runtime::{generic::Era, traits::Hash, AccountId32},
+    Config, DefaultConfig, Encoded, PolkadotExtrinsicParams, PolkadotExtrinsicParamsBuilder, TransactionEvents,
+    TransactionProgress,
+};
+
+pub type TxEvents = TransactionEvents<DefaultConfig, polkadot::Event>;
+
+pub type TxProgress<'a> = TransactionProgress<'a, DefaultConfig, polkadot::DispatchError, polkadot::Event>;
+
+/// Hands out consecutive nonces for an account so that multiple extrinsics can be in the
+/// transaction pool at the same time.
+#[derive(Debug)]
+pub struct NonceManager {
+    next: AtomicU32,
+}
+
+impl NonceManager {
+    /// Starts from the next nonce the node expects, including pending extrinsics in the pool.
+    pub async fn new(api: PolkadotRuntimeApi, account_id: &AccountId32) -> Result<Self, Box<dyn Error>> {
+        let next = api.client.rpc().system_account_next_index(account_id).await?;
+        Ok(Self {
+            next: AtomicU32::new(next),
+        })
+    }
+
+    pub fn next(&self) -> u32 {
+        self.next.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+/// Encodes a call which is not in the embedded metadata, e.g. `utility.force_batch`, using the
+/// pallet and call index of the connected runtime.
+pub fn encode_dynamic_call(
+    api: &PolkadotRuntimeApi,
+    pallet_name: &str,
+    call_name: &str,
+    params: &impl Encode,
+) -> Result<Encoded, Box<dyn Error>> {
+    let locked_metadata = api.client.metadata();
+    let metadata = locked_metadata.read();
+    let pallet = metadata
+        .runtime_metadata()
+        .pallets
+        .iter()
+        .find(|pallet| pallet.name == pallet_name)
+        .ok_or(format!("The connected runtime does not have a `{pallet_name}` pallet"))?;
+    let call_index = pallet
+        .calls
+        .as_ref()
+        .and_then(|calls| metadata.resolve_type(calls.ty.id()))
+        .and_then(|ty| match ty.type_def() {
+            TypeDef::Variant(variants) => variants
+                .variants()
+                .iter()
+                .find(|variant| variant.name() == call_name)
+                .map(|variant| variant.index()),
+            _ => None,
+        })
+        .ok_or(format!(
+            "The connected runtime does not support `{pallet_name}.{call_name}`"
+        ))?;
+
+    let mut call_data = vec![pallet.index, call_index];
+    params.encode_to(&mut call_data);
+    Ok(Encoded(call_data))
+}
+
+/// Signs any `call` from the `polkadot` module, returning the SCALE encoded extrinsic.
+///
+/// The `call` is usually an [`EncodedCall`] but any call data (e.g. [`Encoded`]) can be signed.
+/// The nonce is taken from `nonce`, then the `signer`, and otherwise fetched from the node.
+pub async fn create_signed(
+    api: PolkadotRuntimeApi,
+    call: &impl Encode,
+    signer: &PolkadotSigner,
+    nonce: Option<u32>,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    create_signed_with_params(api, call, signer, nonce, Default::default()).await
+}
+
+/// Same as [`create_signed`] but allows setting the tip and mortality.
+pub async fn create_signed_with_params(
+    api: PolkadotRuntimeApi,
+    call: &impl Encode,
+    signer: &PolkadotSigner,
+    nonce: Option<u32>,
+    other_params: PolkadotExtrinsicParamsBuilder<DefaultConfig>,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let nonce = match nonce.or_else(|| signer.nonce()) {
+        Some(nonce) => nonce,
+        None => api.client.rpc().system_account_next_index(signer.account_id()).await?,
+    };
+
+    // the encoded `Call` enum is already the pallet index, call index and params
+    let call_data = call.encode();
+    let params = {
+        let locked_runtime = api.client.runtime_version();
+        let runtime = locked_runtime.read();
+        PolkadotExtrinsicParams::<DefaultConfig>::new(
+            runtime.spec_version,
+            runtime.transaction_version,
+            nonce,
+            *api.client.genesis(),
+            other_params,
+        )
+    };
+
+    let signature = {
+        let mut bytes = call_data.clone();
+        params.encode_extra_to(&mut bytes);
+        params.encode_additional_to(&mut bytes);
+        if bytes.len() > 256 {
+            signer.sign(&blake2_256(&bytes))
+        } else {
+            signer.sign(&bytes)
+        }
+    };
+
+    // see `SubmittableExtrinsic::create_signed` in subxt
+    let mut inner = Vec::new();
+    (0b10000000 + 4u8).encode_to(&mut inner);
+    signer.address().encode_to(&mut inner);
+    signature.encode_to(&mut inner);
+    params.encode_extra_to(&mut inner);
+    inner.extend(call_data);
+
+    let mut extrinsic = Compact(inner.len() as u32).encode();
+    extrinsic.extend(inner);
+    Ok(extrinsic)
+}
+
+/// Decodes the call of a signed or unsigned extrinsic, e.g. from the `extrinsics` of a block.
+///
+/// The `extrinsic` is without the length prefix, the signed extra is decoded as in [`create_signed`].
+pub fn decode_call(extrinsic: &[u8]) -> Result<EncodedCall, Box<dyn Error>> {
+    let mut input = extrinsic;
+    let version = u8::decode(&mut input)?;
+    if version & 0b10000000 != 0 {
+        // skip the address, signature, mortality, nonce and tip
+        <(
+            <DefaultConfig as Config>::Address,
+            <DefaultConfig as Config>::Signature,
+            Era,
+            Compact<u32>,
+            Compact<u128>,
+        )>::decode(&mut input)?;
+    }
+    Ok(EncodedCall::decode(&mut input)?)
+}
+
+/// Submits a signed extrinsic, returning once it is accepted into the transaction pool.
+///
+/// Use [`crate::dry_run::DryRunSubmitter`] to dry-run the extrinsic first.
+pub async fn submit(api: &PolkadotRuntimeApi, extrinsic: Vec<u8>) -> Result<TxProgress<'_>, Box<dyn Error>> {
+    let extrinsic = Encoded(extrinsic);
+    let ext_hash = <DefaultConfig as Config>::Hashing::hash_of(&extrinsic);
+    let sub = api.client.rpc().watch_extrinsic(&extrinsic).await?;
+    Ok(TxProgress::new(sub, &api.client, ext_hash))
+}
+
+/// Submits a signed extrinsic and waits for it to be finalized successfully, see [`submit`].
+pub async fn submit_and_watch(api: PolkadotRuntimeApi, extrinsic: Vec<u8>) -> Result<TxEvents, Box<dyn Error>> {
+    Ok(submit(&api, extrinsic).await?.wait_for_finalized_success().await?)
+}
+
+/// Signs and submits any `call`, waiting for it to be finalized successfully.
+///
+/// All helpers in this crate submit through here.
+pub async fn sign_and_submit(
+    api: PolkadotRuntimeApi,
+    call: EncodedCall,
+    signer: &PolkadotSigner,
+) -> Result<TxEvents, Box<dyn Error>> {
+    let extrinsic = create_signed(api.clone(), &call, signer, None).await?;
+    submit_and_watch(api, extrinsic).await
+}
@@ -1,11 +1,10 @@
 use sp_keyring::AccountKeyring;
 use std::error::Error;
 use subxt::{
-    sp_core::sr25519::Pair,
     sp_runtime::{AccountId32, MultiAddress},
-    DefaultConfig, PairSigner,
+    PairSigner,
 };
-use subxt_workshop::{with_default_client, PolkadotRuntimeApi};
+use subxt_workshop::{with_default_client, PolkadotRuntimeApi, PolkadotSigner};
 
 /// # Exercise 06 (A)
 ///
@@ -27,7 +26,7 @@ use subxt_workshop::{with_default_client, PolkadotRuntimeApi};
 /// ```
 pub async fn transfer_balance(
     _api: PolkadotRuntimeApi,
-    _signer: PairSigner<DefaultConfig, Pair>,
+    _signer: &PolkadotSigner,
     _dest: MultiAddress<AccountId32, ()>,
     _amount: u128,
 ) -> Result<(), Box<dyn Error>> {
@@ -51,7 +50,7 @@ async fn should_transfer_balance() -> Result<(), Box<dyn Error>> {
         // make the transfer from alice to bob
         transfer_balance(
             api.clone(),
-            PairSigner::new(AccountKeyring::Alice.pair()),
+            &PairSigner::new(AccountKeyring::Alice.pair()),
             dest_account_id.clone().into(),
             amount,
         )
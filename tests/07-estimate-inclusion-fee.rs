@@ -1,6 +1,4 @@
-use serde::Deserialize;
 use sp_keyring::AccountKeyring;
-use sp_rpc::number::NumberOrHex;
 use std::error::Error;
 use subxt::{
     rpc::{rpc_params, ClientT},
@@ -8,35 +6,12 @@ use subxt::{
 };
 use subxt_workshop::{with_default_client, PolkadotRuntimeApi};
 
-#[derive(Clone, Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct FeeDetails<Balance> {
-    pub inclusion_fee: Option<InclusionFee<Balance>>,
-    #[serde(skip)]
-    pub tip: Balance,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct InclusionFee<Balance> {
-    pub base_fee: Balance,
-    pub len_fee: Balance,
-    pub adjusted_weight_fee: Balance,
-}
-
-impl InclusionFee<NumberOrHex> {
-    pub fn inclusion_fee(&self) -> u128 {
-        let base_fee = self.base_fee.into_u256();
-        let len_fee = self.len_fee.into_u256();
-        let adjusted_weight_fee = self.adjusted_weight_fee.into_u256();
-        (base_fee + len_fee + adjusted_weight_fee).as_u128()
-    }
-}
-
 /// # Exercise 07
 ///
 /// Implement a function to estimate the inclusion fee for an encoded transaction.
 ///
+/// Tip: the response can be decoded as `subxt_workshop::fees::FeeDetails<sp_rpc::number::NumberOrHex>`.
+///
 /// ## Hint
 ///
 /// ```
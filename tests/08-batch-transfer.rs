@@ -1,11 +1,10 @@
 use sp_keyring::AccountKeyring;
 use std::error::Error;
 use subxt::{
-    sp_core::sr25519::Pair,
     sp_runtime::{AccountId32, MultiAddress},
-    DefaultConfig, PairSigner,
+    PairSigner,
 };
-use subxt_workshop::{polkadot, with_default_client, EncodedCall, PolkadotRuntimeApi};
+use subxt_workshop::{polkadot, with_default_client, EncodedCall, PolkadotRuntimeApi, PolkadotSigner};
 
 type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
 
@@ -20,7 +19,7 @@ type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
 /// ```
 pub async fn batch_transfer(
     _api: PolkadotRuntimeApi,
-    _signer: PairSigner<DefaultConfig, Pair>,
+    _signer: &PolkadotSigner,
     _recipients: Vec<(MultiAddress<AccountId32, ()>, u128)>,
 ) -> Result<(), Box<dyn Error>> {
     Ok(())
@@ -55,7 +54,7 @@ async fn should_batch_transfer() -> Result<(), Box<dyn Error>> {
 
         batch_transfer(
             api.clone(),
-            PairSigner::new(AccountKeyring::Alice.pair()),
+            &PairSigner::new(AccountKeyring::Alice.pair()),
             recipients
                 .into_iter()
                 .map(|(account_id, value)| (account_id.into(), value))
@@ -1,7 +1,7 @@
-use sp_keyring::{sr25519::sr25519::Pair, AccountKeyring};
+use sp_keyring::AccountKeyring;
 use std::error::Error;
-use subxt::{sp_runtime::AccountId32, DefaultConfig, PairSigner};
-use subxt_workshop::{polkadot, with_default_client, PolkadotRuntimeApi};
+use subxt::{sp_runtime::AccountId32, PairSigner};
+use subxt_workshop::{polkadot, with_default_client, PolkadotRuntimeApi, PolkadotSigner};
 
 type Proposal = polkadot::runtime_types::pallet_treasury::Proposal<AccountId32, u128>;
 type ProposedEvent = polkadot::treasury::events::Proposed;
@@ -18,7 +18,7 @@ type ProposedEvent = polkadot::treasury::events::Proposed;
 /// ```
 pub async fn propose_spend(
     _api: PolkadotRuntimeApi,
-    _signer: PairSigner<DefaultConfig, Pair>,
+    _signer: &PolkadotSigner,
     _value: u128,
 ) -> Result<u32, Box<dyn Error>> {
     Ok(Default::default())
@@ -52,7 +52,7 @@ async fn should_propose_spend() -> Result<(), Box<dyn Error>> {
         let signer_account_id = signer_account.clone().to_account_id();
         let value = 10_000_000_000;
         // make the proposal, the first `Proposed` event will have the index
-        let proposal_index = propose_spend(api.clone(), PairSigner::new(signer_account.pair()), value).await?;
+        let proposal_index = propose_spend(api.clone(), &PairSigner::new(signer_account.pair()), value).await?;
         // read the treasury pallet constants and calculate the expected deposit
         let bond = calculate_proposal_bond(api.clone(), value)?;
 
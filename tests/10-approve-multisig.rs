@@ -1,21 +1,16 @@
 use futures::StreamExt;
 use sp_keyring::AccountKeyring;
 use std::{error::Error, future::Future, time::Duration};
-use subxt::{
-    codec::{Decode, Encode},
-    sp_core::{blake2_256, sr25519::Pair},
-    sp_runtime::{traits::TrailingZeroInput, AccountId32},
-    DefaultConfig, Event, PairSigner, WrapperKeepOpaque,
+use subxt::{sp_runtime::AccountId32, Event, PairSigner, WrapperKeepOpaque};
+use subxt_workshop::{
+    multisig::MultisigAccount, polkadot, with_default_client, EncodedCall, PolkadotRuntimeApi, PolkadotSigner,
 };
-use subxt_workshop::{polkadot, with_default_client, EncodedCall, PolkadotRuntimeApi};
 
 type NewMultisigEvent = polkadot::multisig::events::NewMultisig;
 type MultisigExecutedEvent = polkadot::multisig::events::MultisigExecuted;
 type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
 type Timepoint = polkadot::runtime_types::pallet_multisig::Timepoint<u32>;
 
-const MAX_WEIGHT: u64 = 1_000_000_000_000;
-
 /// # Exercise 10 (A)
 ///
 /// Implement a function to subscribe to all events and filter `Ev`.
@@ -49,10 +44,12 @@ where
 ///
 /// Implement a function to create a 2-of-2 multisig operation.
 ///
-/// Tip: use `as_multi` with a threshold of `2`.
+/// Tip: use `as_multi` with a threshold of `2` and `subxt_workshop::fees::max_weight` of the call
+/// as the `max_weight`, the call is passed as `WrapperKeepOpaque::from_encoded(call.encode())`
+/// with `subxt::codec::Encode` in scope.
 pub async fn create_multisig(
     _api: PolkadotRuntimeApi,
-    _signer: PairSigner<DefaultConfig, Pair>,
+    _signer: &PolkadotSigner,
     _other_signatories: Vec<AccountId32>,
     _encoded_call: EncodedCall,
 ) -> Result<(), Box<dyn Error>> {
@@ -80,7 +77,7 @@ pub async fn get_timepoint(
 /// Tip: use `approve_as_multi` with a threshold of `2`.
 pub async fn approve_multisig(
     _api: PolkadotRuntimeApi,
-    _signer: PairSigner<DefaultConfig, Pair>,
+    _signer: &PolkadotSigner,
     _other_signatories: Vec<AccountId32>,
     _timepoint: Timepoint,
     _call_hash: [u8; 32],
@@ -88,13 +85,6 @@ pub async fn approve_multisig(
     Ok(())
 }
 
-/// Source: https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/frame/multisig/src/lib.rs#L510-L514
-pub fn multi_account_id(who: &[AccountId32], threshold: u16) -> AccountId32 {
-    let entropy = (b"modlpy/utilisuba", who, threshold).using_encoded(blake2_256);
-    Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
-        .expect("infinite length input; no invalid inputs for type; qed")
-}
-
 #[tokio::test]
 async fn should_approve_multisig() -> Result<(), Box<dyn Error>> {
     with_default_client(|api| async move {
@@ -108,7 +98,7 @@ async fn should_approve_multisig() -> Result<(), Box<dyn Error>> {
                     // bob should sign incoming multisig
                     approve_multisig(
                         api.clone(),
-                        PairSigner::new(AccountKeyring::Bob.pair()),
+                        &PairSigner::new(AccountKeyring::Bob.pair()),
                         vec![event.approving],
                         timepoint,
                         event.call_hash,
@@ -122,13 +112,15 @@ async fn should_approve_multisig() -> Result<(), Box<dyn Error>> {
         }
 
         // compute the multisig account id
-        let account_id = multi_account_id(
-            &[
+        let account_id = MultisigAccount::new(
+            &api,
+            [
                 AccountKeyring::Bob.to_account_id(),
                 AccountKeyring::Alice.to_account_id(),
             ],
             2,
-        );
+        )?
+        .account_id();
 
         // fund the multisig so it can afford the transfer
         api.tx()
@@ -142,7 +134,7 @@ async fn should_approve_multisig() -> Result<(), Box<dyn Error>> {
         // alice should create the multisig operation
         create_multisig(
             api.clone(),
-            PairSigner::new(AccountKeyring::Alice.pair()),
+            &PairSigner::new(AccountKeyring::Alice.pair()),
             vec![AccountKeyring::Bob.to_account_id()],
             EncodedCall::Balances(BalancesCall::transfer {
                 dest: AccountKeyring::Charlie.to_account_id().into(),
@@ -0,0 +1,42 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt_workshop::{
+    address::{self, Address, GENERIC_PREFIX, KUSAMA_PREFIX, POLKADOT_PREFIX},
+    with_default_client,
+};
+
+const ALICE_POLKADOT: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+const ALICE_KUSAMA: &str = "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F";
+const ALICE_GENERIC: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+#[test]
+fn should_encode_and_decode_addresses() -> Result<(), Box<dyn Error>> {
+    let alice = AccountKeyring::Alice.to_account_id();
+    for (prefix, encoded) in [
+        (POLKADOT_PREFIX, ALICE_POLKADOT),
+        (KUSAMA_PREFIX, ALICE_KUSAMA),
+        (GENERIC_PREFIX, ALICE_GENERIC),
+    ] {
+        assert_eq!(address::encode(&alice, prefix), encoded);
+        assert_eq!(address::decode(encoded)?, (alice.clone(), prefix));
+    }
+
+    assert!(Address::parse_for(ALICE_GENERIC, POLKADOT_PREFIX).is_ok());
+    assert!(Address::parse_for(ALICE_KUSAMA, POLKADOT_PREFIX).is_err());
+    // change the last character to break the checksum
+    let mut invalid = ALICE_POLKADOT.to_string();
+    invalid.pop();
+    invalid.push('6');
+    assert!(address::decode(&invalid).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_format_for_chain() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let alice = Address::for_chain(&api, AccountKeyring::Alice.to_account_id())?;
+        assert_eq!(alice.to_string(), ALICE_POLKADOT);
+        Ok(())
+    })
+    .await
+}
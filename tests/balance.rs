@@ -0,0 +1,62 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::sp_core::{sr25519, Pair};
+use subxt_workshop::{
+    balance::{get_balance, get_balance_breakdown, Token},
+    with_default_client,
+};
+
+#[test]
+fn should_format_and_parse_balances() {
+    let dot = Token::new(10, "DOT");
+    assert_eq!(dot.balance(10_000_000_000).to_string(), "1.0000 DOT");
+    assert_eq!(dot.balance(12_345_678_901).to_string(), "1.2345 DOT");
+    assert_eq!(format!("{:.2}", dot.balance(1)), "0.00 DOT");
+    assert_eq!(format!("{:.10}", dot.balance(1)), "0.0000000001 DOT");
+
+    assert_eq!(dot.parse("2.5 DOT"), Ok(dot.balance(25_000_000_000)));
+    assert_eq!(dot.parse("2"), Ok(dot.balance(20_000_000_000)));
+    assert_eq!(dot.parse(".0000000001"), Ok(dot.balance(1)));
+    // more precision than a float would keep
+    assert_eq!(
+        dot.parse("123456789.0123456789"),
+        Ok(dot.balance(1_234_567_890_123_456_789))
+    );
+    assert!(dot.parse("0.00000000001").is_err());
+    assert!(dot.parse("2.5 KSM").is_err());
+    assert!(dot.parse("1e3").is_err());
+}
+
+#[tokio::test]
+async fn should_get_balance_with_token() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let balance = get_balance(api.clone(), AccountKeyring::Alice.to_account_id()).await?;
+        assert_eq!(balance.token, Token::new(10, "DOT"));
+        assert!(balance.to_string().ends_with(" DOT"));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_get_balance_breakdown() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        // the dev chain bonds the stash of every validator
+        let stash = sr25519::Pair::from_string("//Alice//stash", None).map_err(|err| format!("{err:?}"))?;
+        let breakdown = get_balance_breakdown(api.clone(), stash.public().into()).await?;
+
+        let staking = breakdown
+            .locks
+            .iter()
+            .find(|lock| lock.id == "staking")
+            .ok_or("Stash is not bonded!")?;
+        assert_eq!(staking.reason(), Some("Staking"));
+        assert!(breakdown.locked() >= staking.amount);
+        assert_eq!(
+            breakdown.transferable.plancks,
+            breakdown.free.plancks - breakdown.locked().plancks
+        );
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,45 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::PairSigner;
+use subxt_workshop::{
+    batch::{submit_batch, BatchItemError, BatchMode},
+    polkadot, with_default_client, EncodedCall,
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+
+#[tokio::test]
+async fn should_report_interrupted_batch() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let transfer = |account: AccountKeyring, value| {
+            EncodedCall::Balances(BalancesCall::transfer {
+                dest: account.to_account_id().into(),
+                value,
+            })
+        };
+        let calls = vec![
+            transfer(AccountKeyring::Bob, 1_000_000_000),
+            // far more than the endowment so the batch stops here
+            transfer(AccountKeyring::Charlie, u128::MAX / 2),
+            transfer(AccountKeyring::Dave, 1_000_000_000),
+        ];
+
+        let results = submit_batch(
+            api.clone(),
+            &PairSigner::new(AccountKeyring::Alice.pair()),
+            BatchMode::Batch,
+            calls,
+        )
+        .await?;
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err(BatchItemError::Failed("Balances::InsufficientBalance".to_string())),
+                Err(BatchItemError::NotExecuted),
+            ]
+        );
+        Ok(())
+    })
+    .await
+}
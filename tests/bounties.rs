@@ -0,0 +1,43 @@
+mod common;
+
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::PairSigner;
+use subxt_workshop::{
+    bounties::{self, BountyStatus},
+    council, deposits, with_default_client,
+};
+
+#[tokio::test]
+async fn should_propose_and_approve_bounty() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let description = "Write more examples";
+        let bounty_id = bounties::propose_bounty(api.clone(), &alice, 10_000_000_000_000, description).await?;
+
+        let bounty = bounties::get_bounties(api.clone(), None)
+            .await?
+            .into_iter()
+            .find(|bounty| bounty.index == bounty_id)
+            .ok_or("Bounty not found!")?;
+        assert_eq!(bounty.status, BountyStatus::Proposed);
+        assert_eq!(bounty.description, description);
+        assert_eq!(bounty.bond, deposits::bounty_deposit(&api, description.len())?);
+
+        // the development council has 6 members, approving needs 3/5 of them
+        let motion = bounties::approve_bounty(api.clone(), &alice, bounty_id, 4).await?;
+        for member in common::COUNCIL {
+            council::vote_motion(api.clone(), &PairSigner::new(member.pair()), &motion, true).await?;
+        }
+        council::close_motion(api.clone(), &alice, &motion).await?;
+
+        let bounty = bounties::get_bounties(api.clone(), None)
+            .await?
+            .into_iter()
+            .find(|bounty| bounty.index == bounty_id)
+            .ok_or("Bounty not found!")?;
+        assert_eq!(bounty.status, BountyStatus::Approved);
+        Ok(())
+    })
+    .await
+}
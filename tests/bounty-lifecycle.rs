@@ -0,0 +1,86 @@
+mod common;
+
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::{sp_core::sr25519, DefaultConfig, PairSigner};
+use subxt_workshop::{
+    balance::get_balance,
+    bounties::{self, BountyStatus},
+    council, with_default_client, PolkadotRuntimeApi,
+};
+
+/// Waits for a finalized block where the status of the bounty and the block number match `done`,
+/// returning the status.
+async fn wait_for_bounty(
+    api: PolkadotRuntimeApi,
+    bounty_id: u32,
+    done: impl Fn(&BountyStatus, u32) -> bool,
+) -> Result<BountyStatus, Box<dyn Error>> {
+    let mut heads = api.client.rpc().subscribe_finalized_blocks().await?;
+    while let Some(head) = heads.next().await {
+        let head = head?;
+        let bounty = bounties::get_bounties(api.clone(), Some(head.hash()))
+            .await?
+            .into_iter()
+            .find(|bounty| bounty.index == bounty_id)
+            .ok_or("Bounty not found!")?;
+        if done(&bounty.status, head.number) {
+            return Ok(bounty.status);
+        }
+    }
+    Err("Finalized blocks subscription ended".into())
+}
+
+/// Passes a council motion, the development council has 6 members and needs 3/5 of them.
+async fn pass_motion(
+    api: PolkadotRuntimeApi,
+    alice: &PairSigner<DefaultConfig, sr25519::Pair>,
+    motion: council::Motion,
+) -> Result<(), Box<dyn Error>> {
+    for member in common::COUNCIL {
+        council::vote_motion(api.clone(), &PairSigner::new(member.pair()), &motion, true).await?;
+    }
+    council::close_motion(api, alice, &motion).await
+}
+
+// funding waits for the next treasury spend and claiming for the `BountyDepositPayoutDelay`,
+// which are 24 and 8 days on the development chain
+#[tokio::test]
+#[ignore = "takes the SpendPeriod and BountyDepositPayoutDelay of the chain"]
+async fn should_award_and_claim_bounty() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let curator = common::funded_account(api.clone(), &alice, "//BountyCurator", 100_000_000_000_000).await?;
+        let beneficiary = common::funded_account(api.clone(), &alice, "//BountyBeneficiary", 100_000_000_000).await?;
+        let (value, fee) = (10_000_000_000_000, 1_000_000_000_000);
+
+        let bounty_id = bounties::propose_bounty(api.clone(), &alice, value, "Maintain the workshop").await?;
+        let motion = bounties::approve_bounty(api.clone(), &alice, bounty_id, 4).await?;
+        pass_motion(api.clone(), &alice, motion).await?;
+        wait_for_bounty(api.clone(), bounty_id, |status, _| *status == BountyStatus::Funded).await?;
+
+        let motion =
+            bounties::propose_curator(api.clone(), &alice, bounty_id, curator.account_id().clone(), fee, 4).await?;
+        pass_motion(api.clone(), &alice, motion).await?;
+        bounties::accept_curator(api.clone(), &curator, bounty_id).await?;
+        bounties::award_bounty(api.clone(), &curator, bounty_id, beneficiary.account_id().clone()).await?;
+
+        let status = wait_for_bounty(api.clone(), bounty_id, |status, block_number| {
+            matches!(status, BountyStatus::PendingPayout { unlock_at, .. } if block_number >= *unlock_at)
+        })
+        .await?;
+        assert!(matches!(
+            &status,
+            BountyStatus::PendingPayout { curator: awarded_by, beneficiary: awarded_to, .. }
+                if awarded_by == curator.account_id() && awarded_to == beneficiary.account_id()
+        ));
+
+        let before = get_balance(api.clone(), beneficiary.account_id().clone()).await?;
+        let payout = bounties::claim_bounty(api.clone(), &curator, bounty_id).await?;
+        assert_eq!(payout, value - fee);
+        let after = get_balance(api.clone(), beneficiary.account_id().clone()).await?;
+        assert_eq!(after.plancks - before.plancks, payout);
+        Ok(())
+    })
+    .await
+}
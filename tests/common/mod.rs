@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+use futures::lock::Mutex;
+use sp_keyring::AccountKeyring;
+use std::{error::Error, sync::OnceLock};
+use subxt::{
+    sp_core::{sr25519, Pair},
+    sp_runtime::AccountId32,
+    DefaultConfig, PairSigner,
+};
+use subxt_workshop::{transfer, PolkadotRuntimeApi, PolkadotSigner};
+
+/// The members of the development council the tests vote and tip with, besides Alice who proposes.
+///
+/// Dave is left out as exercise 02 checks its balance and Ferdie as the fee estimator test relies
+/// on its nonce. With Alice that is 4 of the 6 members, enough for a 3/5 majority.
+pub const COUNCIL: [AccountKeyring; 3] = [AccountKeyring::Bob, AccountKeyring::Charlie, AccountKeyring::Eve];
+
+/// Tests of one binary run in parallel, so transfers from the same funder would race for its nonce.
+static FUNDING: OnceLock<Mutex<()>> = OnceLock::new();
+
+/// Transfers `amount` from the `funder` to the account derived from the dev seed with `path`,
+/// e.g. `//TransferAll`, so each test signs with its own account.
+pub async fn funded_account(
+    api: PolkadotRuntimeApi,
+    funder: &PolkadotSigner,
+    path: &str,
+    amount: u128,
+) -> Result<PairSigner<DefaultConfig, sr25519::Pair>, Box<dyn Error>> {
+    let pair = sr25519::Pair::from_string(path, None).map_err(|err| format!("{err:?}"))?;
+    let account: AccountId32 = pair.public().into();
+    let _guard = FUNDING.get_or_init(|| Mutex::new(())).lock().await;
+    transfer::transfer_keep_alive(api, funder, account.into(), amount).await?;
+    Ok(PairSigner::new(pair))
+}
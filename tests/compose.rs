@@ -0,0 +1,194 @@
+mod common;
+
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::{
+    sp_core::{sr25519, Pair},
+    sp_runtime::AccountId32,
+    PairSigner,
+};
+use subxt_workshop::{
+    compose::{CallComposer, LayerKind, ProxyType},
+    fees,
+    multisig::{self, Approval, CallStore, MultisigAccount, MultisigCoordinator},
+    polkadot, transfer, tx, with_default_client, EncodedCall,
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+type MultisigCall = polkadot::runtime_types::pallet_multisig::pallet::Call;
+type ProxyCall = polkadot::runtime_types::pallet_proxy::pallet::Call;
+type SystemCall = polkadot::runtime_types::frame_system::pallet::Call;
+type UtilityCall = polkadot::runtime_types::pallet_utility::pallet::Call;
+
+#[tokio::test]
+async fn should_execute_proxy_call_from_multisig() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let store = CallStore::open(std::env::temp_dir().join("subxt-workshop-compose"))?;
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let proposer = common::funded_account(api.clone(), &eve, "//ComposeProxyProposer", 1_000_000_000_000).await?;
+        let approver = common::funded_account(api.clone(), &eve, "//ComposeProxyApprover", 100_000_000_000).await?;
+        let multisig = MultisigAccount::new(&api, [proposer.account_id().clone(), approver.account_id().clone()], 2)?;
+        let coordinator = MultisigCoordinator::new(api.clone(), multisig, store)?;
+        transfer::transfer_keep_alive(api.clone(), &proposer, coordinator.account().into(), 100_000_000_000).await?;
+
+        // the multisig controls an account the exercises do not use through a proxy
+        let real_signer = common::funded_account(api.clone(), &eve, "//ComposeProxy", 1_000_000_000_000).await?;
+        let real = real_signer.account_id().clone();
+        let add_proxy = EncodedCall::Proxy(ProxyCall::add_proxy {
+            delegate: coordinator.account(),
+            proxy_type: ProxyType::Any,
+            delay: 0,
+        });
+        tx::sign_and_submit(api.clone(), add_proxy, &real_signer).await?;
+
+        let dest = sr25519::Pair::from_string("//ComposeDest", None)
+            .map_err(|err| format!("{err:?}"))?
+            .public();
+        let transfer = EncodedCall::Balances(BalancesCall::transfer {
+            dest: AccountId32::from(dest).into(),
+            value: 10_000_000_000,
+        });
+        let transfer_hash = multisig::call_hash(&transfer);
+        let composed = CallComposer::new(api.clone(), transfer)
+            .proxy(real.clone(), None)
+            .build()
+            .await?;
+        assert_eq!(composed.layers.len(), 2);
+        assert_eq!(composed.layers[0].call_hash, transfer_hash);
+        assert_eq!(composed.layers[1].kind, LayerKind::Proxy { real });
+        assert!(composed.weight() > composed.layers[0].weight);
+
+        let call_hash = coordinator.propose(&proposer, &composed.call).await?;
+        assert_eq!(call_hash, composed.call_hash());
+        let approval = coordinator.approve(&approver, &call_hash).await?;
+        assert_eq!(approval, Approval::Executed);
+
+        let remove_proxies = EncodedCall::Proxy(ProxyCall::remove_proxies);
+        tx::sign_and_submit(api.clone(), remove_proxies, &real_signer).await?;
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_compose_batch_all_in_proxy_and_multisig() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let remarks = || {
+            ["first", "second"]
+                .into_iter()
+                .map(|remark| {
+                    EncodedCall::System(SystemCall::remark {
+                        remark: remark.as_bytes().to_vec(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        let batch_all = || EncodedCall::Utility(UtilityCall::batch_all { calls: remarks() });
+        let real = AccountKeyring::Eve.to_account_id();
+        let multisig = MultisigAccount::new(
+            &api,
+            [
+                AccountKeyring::Alice.to_account_id(),
+                AccountKeyring::Bob.to_account_id(),
+            ],
+            2,
+        )?;
+
+        let composed = CallComposer::batch_all(api.clone(), remarks())
+            .proxy(real.clone(), None)
+            .as_multi(multisig.clone(), AccountKeyring::Alice.to_account_id())
+            .build()
+            .await?;
+        assert_eq!(composed.layers.len(), 3);
+
+        let batch = &composed.layers[0];
+        assert_eq!(batch.kind, LayerKind::BatchAll { len: 2 });
+        assert_eq!(batch.call_hash, multisig::call_hash(&batch_all()));
+        assert_eq!(batch.weight, fees::dispatch_weight(api.clone(), &batch_all()).await?);
+
+        let proxy = EncodedCall::Proxy(ProxyCall::proxy {
+            real: real.clone(),
+            force_proxy_type: None,
+            call: Box::new(batch_all()),
+        });
+        assert_eq!(composed.layers[1].kind, LayerKind::Proxy { real });
+        assert_eq!(composed.layers[1].call_hash, multisig::call_hash(&proxy));
+        assert!(composed.layers[1].weight > batch.weight);
+
+        assert_eq!(
+            composed.layers[2].kind,
+            LayerKind::AsMulti {
+                multisig: multisig.account_id(),
+                signatory: AccountKeyring::Alice.to_account_id(),
+                max_weight: fees::with_weight_margin(composed.layers[1].weight),
+            }
+        );
+        assert!(composed.weight() > composed.layers[1].weight);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_approve_nested_multisig() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let store = CallStore::open(std::env::temp_dir().join("subxt-workshop-compose"))?;
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let proposer = common::funded_account(api.clone(), &eve, "//ComposeNestedProposer", 1_000_000_000_000).await?;
+        let approver = common::funded_account(api.clone(), &eve, "//ComposeNestedApprover", 100_000_000_000).await?;
+        let outer = MultisigAccount::new(&api, [proposer.account_id().clone(), approver.account_id().clone()], 2)?;
+        let coordinator = MultisigCoordinator::new(api.clone(), outer, store)?;
+        // the outer multisig reserves the deposit of the inner operation
+        transfer::transfer_keep_alive(api.clone(), &proposer, coordinator.account().into(), 500_000_000_000).await?;
+
+        let inner = MultisigAccount::new(&api, [coordinator.account(), AccountKeyring::Eve.to_account_id()], 2)?;
+        let remark = EncodedCall::System(SystemCall::remark {
+            remark: format!("{:?}", std::time::SystemTime::now()).into_bytes(),
+        });
+        let remark_hash = multisig::call_hash(&remark);
+        let composed = CallComposer::new(api.clone(), remark)
+            .as_multi(inner.clone(), coordinator.account())
+            .build()
+            .await?;
+        assert_eq!(
+            composed.layers[1].kind,
+            LayerKind::AsMulti {
+                multisig: inner.account_id(),
+                signatory: coordinator.account(),
+                max_weight: fees::with_weight_margin(composed.layers[0].weight),
+            }
+        );
+
+        let call_hash = coordinator.propose(&proposer, &composed.call).await?;
+        let approval = coordinator.approve(&approver, &call_hash).await?;
+        assert_eq!(approval, Approval::Executed);
+
+        // the outer multisig approved the remark as a signatory of the inner one
+        let pending = api
+            .storage()
+            .multisig()
+            .multisigs(&inner.account_id(), &remark_hash, None)
+            .await?
+            .ok_or("Inner multisig not found!")?;
+        assert_eq!(pending.approvals, vec![coordinator.account()]);
+
+        // the outer multisig is the depositor, so it has to cancel the inner operation
+        let cancel = EncodedCall::Multisig(MultisigCall::cancel_as_multi {
+            threshold: inner.threshold(),
+            other_signatories: inner.other_signatories(&coordinator.account())?,
+            timepoint: pending.when,
+            call_hash: remark_hash,
+        });
+        let cancel_hash = coordinator.propose(&proposer, &cancel).await?;
+        let approval = coordinator.approve(&approver, &cancel_hash).await?;
+        assert_eq!(approval, Approval::Executed);
+        assert!(api
+            .storage()
+            .multisig()
+            .multisigs(&inner.account_id(), &remark_hash, None)
+            .await?
+            .is_none());
+        Ok(())
+    })
+    .await
+}
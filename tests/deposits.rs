@@ -0,0 +1,71 @@
+mod common;
+
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::PairSigner;
+use subxt_workshop::{deposits, polkadot, tx, with_default_client, EncodedCall};
+
+type TreasuryCall = polkadot::runtime_types::pallet_treasury::pallet::Call;
+type ProxyCall = polkadot::runtime_types::pallet_proxy::pallet::Call;
+type ProxyType = polkadot::runtime_types::polkadot_runtime::ProxyType;
+type ProposedEvent = polkadot::treasury::events::Proposed;
+
+#[tokio::test]
+async fn should_match_treasury_proposal_bond() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let value = 10_000_000_000_000;
+        let call = EncodedCall::Treasury(TreasuryCall::propose_spend {
+            value,
+            beneficiary: AccountKeyring::Bob.to_account_id().into(),
+        });
+        let events = tx::sign_and_submit(api.clone(), call, &PairSigner::new(AccountKeyring::Alice.pair())).await?;
+        let proposal_index = events
+            .find_first::<ProposedEvent>()?
+            .ok_or("Not proposed!")?
+            .proposal_index;
+        let proposal = api
+            .storage()
+            .treasury()
+            .proposals(&proposal_index, Some(events.block_hash()))
+            .await?
+            .ok_or("Proposal not found!")?;
+
+        assert_eq!(proposal.bond, deposits::treasury_proposal_bond(&api, value)?);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_match_proxy_deposit() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let signer = common::funded_account(api.clone(), &eve, "//ProxyDeposit", 1_000_000_000_000).await?;
+        let account = signer.account_id().clone();
+
+        let add_proxy = EncodedCall::Proxy(ProxyCall::add_proxy {
+            delegate: AccountKeyring::Eve.to_account_id(),
+            proxy_type: ProxyType::Any,
+            delay: 0,
+        });
+        let added = tx::sign_and_submit(api.clone(), add_proxy, &signer).await?.block_hash();
+        let remove_proxies = EncodedCall::Proxy(ProxyCall::remove_proxies);
+        let removed = tx::sign_and_submit(api.clone(), remove_proxies, &signer)
+            .await?
+            .block_hash();
+
+        let reserved = |block_hash| {
+            let (api, account) = (api.clone(), account.clone());
+            async move { api.storage().system().account(&account, Some(block_hash)).await }
+        };
+        let (added, removed) = (
+            reserved(added).await?.data.reserved,
+            reserved(removed).await?.data.reserved,
+        );
+
+        assert_eq!(added - removed, deposits::proxy_deposit(&api, 1)?);
+        assert_eq!(deposits::proxy_deposit(&api, 0)?, 0);
+        Ok(())
+    })
+    .await
+}
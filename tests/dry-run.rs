@@ -0,0 +1,75 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::PairSigner;
+use subxt_workshop::{
+    dry_run::{dry_run, DryRunError, DryRunSubmitter},
+    polkadot, tx, with_default_client, EncodedCall,
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+
+#[tokio::test]
+async fn should_dry_run_transfer() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let signer = PairSigner::new(AccountKeyring::Eve.pair());
+        let transfer = |value| {
+            EncodedCall::Balances(BalancesCall::transfer {
+                dest: AccountKeyring::Ferdie.to_account_id().into(),
+                value,
+            })
+        };
+
+        let signed = tx::create_signed(api.clone(), &transfer(10_000_000_000), &signer, None).await?;
+        assert!(
+            dry_run(api.clone(), &signed, None).await?.is_ok(),
+            "Transfer should succeed!"
+        );
+
+        // far more than the endowment so the balances pallet rejects it
+        let signed = tx::create_signed(api.clone(), &transfer(u128::MAX / 2), &signer, None).await?;
+        match dry_run(api.clone(), &signed, None).await? {
+            Err(DryRunError::Module(err)) => {
+                assert_eq!(
+                    (err.pallet.as_str(), err.error.as_str()),
+                    ("Balances", "InsufficientBalance")
+                )
+            }
+            result => panic!("Unexpected dry-run result: {result:?}"),
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_submit_failing_call() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let signer = PairSigner::new(AccountKeyring::Eve.pair());
+        let nonce = api
+            .client
+            .rpc()
+            .system_account_next_index(&AccountKeyring::Eve.to_account_id())
+            .await?;
+
+        let transfer = EncodedCall::Balances(BalancesCall::transfer {
+            dest: AccountKeyring::Ferdie.to_account_id().into(),
+            value: u128::MAX / 2,
+        });
+        let err = DryRunSubmitter::new(api.clone())
+            .sign_and_submit(transfer, &signer)
+            .await
+            .err()
+            .ok_or("Failing call was submitted!")?;
+        assert_eq!(err.to_string(), "Dispatch failed: Balances::InsufficientBalance");
+
+        // nothing was submitted so no fees were paid
+        let next = api
+            .client
+            .rpc()
+            .system_account_next_index(&AccountKeyring::Eve.to_account_id())
+            .await?;
+        assert_eq!(next, nonce);
+        Ok(())
+    })
+    .await
+}
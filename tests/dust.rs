@@ -0,0 +1,53 @@
+use std::error::Error;
+use subxt_workshop::{
+    accounts::{AccountData, AccountInfo},
+    dust::{assess, DustScanner, Risk},
+    with_default_client,
+};
+
+fn account(free: u128, consumers: u32, providers: u32) -> AccountInfo {
+    AccountInfo {
+        nonce: 0,
+        consumers,
+        providers,
+        sufficients: 0,
+        data: AccountData {
+            free,
+            reserved: 0,
+            misc_frozen: 0,
+            fee_frozen: 0,
+        },
+    }
+}
+
+#[test]
+fn should_assess_risks() {
+    assert_eq!(assess(&account(1_000, 0, 1), 100, 100), vec![]);
+    assert_eq!(
+        assess(&account(200, 0, 1), 100, 100),
+        vec![Risk::NearExistentialDeposit]
+    );
+    assert_eq!(
+        assess(&account(1_000, 2, 1), 100, 100),
+        vec![Risk::ConsumersBlockReaping]
+    );
+    assert_eq!(
+        assess(&account(0, 0, 0), 100, 100),
+        vec![Risk::NearExistentialDeposit, Risk::NoProviders]
+    );
+}
+
+#[tokio::test]
+async fn should_scan_for_dust() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let report = DustScanner::new(api.clone()).scan().await?;
+        // the bonded validator stashes have consumers
+        assert!(report.with_risk(Risk::ConsumersBlockReaping).count() > 0);
+
+        // with a huge margin every account is near the existential deposit
+        let report = DustScanner::new(api.clone()).margin(u128::MAX).scan().await?;
+        assert_eq!(report.with_risk(Risk::NearExistentialDeposit).count(), report.accounts);
+        Ok(())
+    })
+    .await
+}
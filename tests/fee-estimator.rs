@@ -0,0 +1,88 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::PairSigner;
+use subxt_workshop::{
+    fees::{self, estimate_fee, DispatchClass, FeeEstimator},
+    polkadot, with_default_client, EncodedCall,
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+
+#[test]
+fn should_add_weight_margin() {
+    assert_eq!(fees::with_weight_margin(0), 0);
+    assert_eq!(fees::with_weight_margin(1_000_000), 1_100_000);
+    assert_eq!(fees::with_weight_margin(u64::MAX), u64::MAX);
+}
+
+#[tokio::test]
+async fn should_estimate_fee_breakdown() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let call = EncodedCall::Balances(BalancesCall::transfer {
+            dest: AccountKeyring::Bob.to_account_id().into(),
+            value: 10_000_000_000,
+        });
+        let estimator = FeeEstimator::new(api.clone());
+
+        let signed = estimator
+            .estimate(&call, Some(&PairSigner::new(AccountKeyring::Alice.pair())))
+            .await?;
+        assert!(signed.weight > 0, "Transfer should have a weight!");
+        assert_eq!(signed.class, DispatchClass::Normal);
+        assert_eq!(signed.partial_fee, signed.inclusion_fee(), "Fee parts do not add up!");
+
+        // the length may differ slightly with the nonce but the weight must match
+        let unsigned = estimator.estimate(&call, None).await?;
+        assert_eq!(unsigned.weight, signed.weight, "Dummy signed weight does not match!");
+
+        let tipped = estimator.tip(1_000).estimate(&call, None).await?;
+        assert!(tipped.total() > unsigned.total() + 1_000, "Tip is not included!");
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_estimate_fee_without_key() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        // ferdie is not used to submit elsewhere so the nonce is stable
+        let sender = AccountKeyring::Ferdie;
+        let call = EncodedCall::Balances(BalancesCall::transfer {
+            dest: AccountKeyring::Bob.to_account_id().into(),
+            value: 10_000_000_000,
+        });
+
+        let signed = FeeEstimator::new(api.clone())
+            .estimate(&call, Some(&PairSigner::new(sender.pair())))
+            .await?;
+        assert_eq!(
+            estimate_fee(api.clone(), &call, sender.to_account_id()).await?.plancks,
+            signed.total(),
+            "Fee without key does not match signed fee!"
+        );
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_add_margin_to_dispatch_weight() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let call = EncodedCall::Balances(BalancesCall::transfer {
+            dest: AccountKeyring::Bob.to_account_id().into(),
+            value: 10_000,
+        });
+        let dispatch_weight = fees::dispatch_weight(api.clone(), &call).await?;
+        assert_eq!(
+            dispatch_weight,
+            FeeEstimator::new(api.clone()).estimate(&call, None).await?.weight
+        );
+        assert_eq!(
+            fees::max_weight(api.clone(), &call).await?,
+            fees::with_weight_margin(dispatch_weight)
+        );
+        assert!(fees::with_weight_margin(dispatch_weight) > dispatch_weight);
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,19 @@
+use std::error::Error;
+use subxt_workshop::{issuance::reconcile_issuance, with_default_client};
+
+#[tokio::test]
+async fn should_reconcile_issuance() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let report = reconcile_issuance(api.clone(), None, 3).await?;
+        assert_eq!(report.discrepancy(), 0, "Balances do not add up to the total issuance!");
+        assert_eq!(report.top_holders.len(), 3);
+        assert!(report
+            .top_holders
+            .windows(2)
+            .all(|holders| holders[0].total() >= holders[1].total()));
+        // polkadot v0.9.18 does not track inactive issuance
+        assert_eq!(report.inactive_issuance, None);
+        Ok(())
+    })
+    .await
+}
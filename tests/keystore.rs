@@ -0,0 +1,90 @@
+use crypto_secretbox::{aead::Aead, KeyInit, XSalsa20Poly1305};
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::sp_core::{crypto::Ss58Codec, Pair};
+use subxt_workshop::keystore::{self, KeyType};
+
+const DEV_PHRASE: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+/// Encrypts the `sr25519` dev account in the same layout as a polkadot-js export.
+fn encrypt_json(keyring: AccountKeyring, password: &str) -> String {
+    let pair = keyring.pair();
+    let keypair: &schnorrkel::Keypair = pair.as_ref();
+    let mut pkcs8 = vec![48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+    pkcs8.extend(keypair.secret.to_ed25519_bytes());
+    pkcs8.extend([161, 35, 3, 33, 0]);
+    pkcs8.extend(pair.public().0);
+
+    let salt = [7u8; 32];
+    let (n, p, r) = (1u32 << 10, 1u32, 8u32);
+    let mut key = [0u8; 32];
+    scrypt::scrypt(
+        password.as_bytes(),
+        &salt,
+        &scrypt::Params::new(10, r, p, 32).unwrap(),
+        &mut key,
+    )
+    .unwrap();
+    let nonce = [3u8; 24];
+    let ciphertext = XSalsa20Poly1305::new(&key.into())
+        .encrypt(&nonce.into(), &pkcs8[..])
+        .unwrap();
+
+    let mut encoded = salt.to_vec();
+    encoded.extend(n.to_le_bytes());
+    encoded.extend(p.to_le_bytes());
+    encoded.extend(r.to_le_bytes());
+    encoded.extend(nonce);
+    encoded.extend(ciphertext);
+
+    serde_json::json!({
+        "encoded": base64::Engine::encode(&base64::engine::general_purpose::STANDARD, encoded),
+        "encoding": {
+            "content": ["pkcs8", "sr25519"],
+            "type": ["scrypt", "xsalsa20-poly1305"],
+            "version": "3"
+        },
+        "address": keyring.to_account_id().to_ss58check(),
+        "meta": { "name": "test" }
+    })
+    .to_string()
+}
+
+#[test]
+fn should_load_signer_from_uri() -> Result<(), Box<dyn Error>> {
+    let signer = keystore::from_uri("//Alice", KeyType::Sr25519)?;
+    assert_eq!(
+        signer.account_id(),
+        &AccountKeyring::Alice.to_account_id(),
+        "Account does not match!"
+    );
+    Ok(())
+}
+
+#[test]
+fn should_load_signer_from_mnemonic() -> Result<(), Box<dyn Error>> {
+    let signer = keystore::from_mnemonic(DEV_PHRASE, "//Bob", None, KeyType::Sr25519)?;
+    assert_eq!(
+        signer.account_id(),
+        &AccountKeyring::Bob.to_account_id(),
+        "Account does not match!"
+    );
+    assert!(keystore::from_mnemonic(DEV_PHRASE, "Bob", None, KeyType::Sr25519).is_err());
+    Ok(())
+}
+
+#[test]
+fn should_load_signer_from_json() -> Result<(), Box<dyn Error>> {
+    let json = encrypt_json(AccountKeyring::Charlie, "hunter2");
+    let signer = keystore::from_json(&json, "hunter2")?;
+    assert_eq!(
+        signer.account_id(),
+        &AccountKeyring::Charlie.to_account_id(),
+        "Account does not match!"
+    );
+    assert!(
+        keystore::from_json(&json, "wrong").is_err(),
+        "Wrong password should not decrypt!"
+    );
+    Ok(())
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::{
+    codec::Encode,
+    sp_core::{sr25519, Pair},
+    sp_runtime::AccountId32,
+    PairSigner, WrapperKeepOpaque,
+};
+use subxt_workshop::{
+    address::Address,
+    deposits, fees,
+    multisig::{self, Approval, CallStore, MultisigAccount, MultisigCoordinator},
+    polkadot, transfer, tx, with_default_client, EncodedCall,
+};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+type MultisigCall = polkadot::runtime_types::pallet_multisig::pallet::Call;
+type SystemCall = polkadot::runtime_types::frame_system::pallet::Call;
+
+#[test]
+fn should_derive_same_account_for_any_order() -> Result<(), Box<dyn Error>> {
+    let alice = AccountKeyring::Alice.to_account_id();
+    let bob = AccountKeyring::Bob.to_account_id();
+    let charlie = AccountKeyring::Charlie.to_account_id();
+
+    let multisig = MultisigAccount::with_max_signatories([charlie.clone(), alice.clone(), bob.clone()], 2, 100)?;
+    let reordered =
+        MultisigAccount::with_max_signatories([bob.clone(), charlie.clone(), alice.clone(), bob.clone()], 2, 100)?;
+    assert_eq!(multisig, reordered);
+
+    let mut sorted = vec![alice, bob.clone(), charlie];
+    sorted.sort();
+    assert_eq!(multisig.signatories(), sorted.as_slice());
+    assert_eq!(multisig.account_id(), multisig::multi_account_id(&sorted, 2));
+    assert_ne!(
+        multisig.account_id(),
+        MultisigAccount::with_max_signatories(sorted, 3, 100)?.account_id()
+    );
+
+    let others = multisig.other_signatories(&bob)?;
+    assert_eq!(others.len(), 2);
+    assert!(!others.contains(&bob));
+    assert!(others.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(multisig
+        .other_signatories(&AccountKeyring::Dave.to_account_id())
+        .is_err());
+
+    let address: Address = multisig.address(0).to_string().parse()?;
+    assert_eq!(address.account, multisig.account_id());
+    Ok(())
+}
+
+#[test]
+fn should_validate_threshold_and_signatories() {
+    let alice = AccountKeyring::Alice.to_account_id();
+    let bob = AccountKeyring::Bob.to_account_id();
+    let charlie = AccountKeyring::Charlie.to_account_id();
+
+    assert!(MultisigAccount::with_max_signatories([alice.clone(), bob.clone()], 0, 100).is_err());
+    assert!(MultisigAccount::with_max_signatories([alice.clone(), bob.clone()], 3, 100).is_err());
+    assert!(MultisigAccount::with_max_signatories([alice.clone(), alice.clone()], 1, 100).is_err());
+    assert!(MultisigAccount::with_max_signatories([alice.clone(), bob.clone(), charlie], 2, 2).is_err());
+    assert!(MultisigAccount::with_max_signatories([alice, bob], 2, 2).is_ok());
+}
+
+#[tokio::test]
+async fn should_execute_on_final_approval() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let store = CallStore::open(std::env::temp_dir().join("subxt-workshop-multisig"))?;
+        let multisig = MultisigAccount::new(
+            &api,
+            [
+                AccountKeyring::Charlie.to_account_id(),
+                AccountKeyring::Alice.to_account_id(),
+                AccountKeyring::Bob.to_account_id(),
+            ],
+            2,
+        )?;
+        let coordinator = MultisigCoordinator::new(api.clone(), multisig, store)?;
+
+        // fund the multisig so it can afford the transfer
+        transfer::transfer_keep_alive(
+            api.clone(),
+            &PairSigner::new(AccountKeyring::Alice.pair()),
+            coordinator.account().into(),
+            100_000_000_000,
+        )
+        .await?;
+
+        let dest = sr25519::Pair::from_string("//MultisigDest", None)
+            .map_err(|err| format!("{err:?}"))?
+            .public();
+        let call = EncodedCall::Balances(BalancesCall::transfer {
+            dest: AccountId32::from(dest).into(),
+            value: 10_000_000_000,
+        });
+        let call_hash = coordinator
+            .propose(&PairSigner::new(AccountKeyring::Alice.pair()), &call)
+            .await?;
+        let status = coordinator.status(&call_hash).await?.ok_or("Multisig not found!")?;
+        assert_eq!(status.depositor, AccountKeyring::Alice.to_account_id());
+        assert_eq!(status.approvals, vec![AccountKeyring::Alice.to_account_id()]);
+
+        let approval = coordinator
+            .approve(&PairSigner::new(AccountKeyring::Charlie.pair()), &call_hash)
+            .await?;
+        assert_eq!(approval, Approval::Executed);
+        assert!(coordinator.status(&call_hash).await?.is_none());
+        assert!(coordinator.store().get(&call_hash)?.is_none());
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_decode_pending_multisig_from_history() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        // ferdie never signs, so it needs no funds
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let depositor = common::funded_account(api.clone(), &eve, "//MultisigDecode", 1_000_000_000_000).await?;
+        let multisig = MultisigAccount::new(
+            &api,
+            [depositor.account_id().clone(), AccountKeyring::Ferdie.to_account_id()],
+            2,
+        )?;
+
+        // approve with `as_multi` so the call data is in the extrinsic
+        let call = EncodedCall::System(SystemCall::remark {
+            remark: format!("{:?}", std::time::SystemTime::now()).into_bytes(),
+        });
+        let call_hash = multisig::call_hash(&call);
+        let as_multi = EncodedCall::Multisig(MultisigCall::as_multi {
+            threshold: 2,
+            other_signatories: vec![AccountKeyring::Ferdie.to_account_id()],
+            maybe_timepoint: None,
+            call: WrapperKeepOpaque::from_encoded(call.encode()),
+            store_call: false,
+            max_weight: fees::max_weight(api.clone(), &call).await?,
+        });
+        tx::sign_and_submit(api.clone(), as_multi, &depositor).await?;
+
+        let pending = multisig::pending_multisigs(api.clone(), &multisig.account_id(), None).await?;
+        let operation = pending
+            .iter()
+            .find(|pending| pending.call_hash == call_hash)
+            .ok_or("Multisig not found!")?;
+        assert_eq!(&operation.depositor, depositor.account_id());
+        assert_eq!(operation.approvals, vec![depositor.account_id().clone()]);
+        let decoded = operation.call.as_ref().ok_or("Call not decoded!")?;
+        assert_eq!(multisig::call_hash(decoded), call_hash);
+
+        // cancel so the deposit is not left reserved
+        multisig::cancel_multisig(api.clone(), &depositor, &multisig, call_hash).await?;
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_cancel_and_reclaim_deposit() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let store = CallStore::open(std::env::temp_dir().join("subxt-workshop-multisig"))?;
+        // the other signatory never signs, so it needs no funds
+        let other = sr25519::Pair::from_string("//MultisigCancel", None)
+            .map_err(|err| format!("{err:?}"))?
+            .public();
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let proposer = common::funded_account(api.clone(), &eve, "//MultisigCancelProposer", 1_000_000_000_000).await?;
+        let multisig = MultisigAccount::new(&api, [proposer.account_id().clone(), other.into()], 2)?;
+        let coordinator = MultisigCoordinator::new(api.clone(), multisig, store)?;
+        let call = EncodedCall::System(SystemCall::remark {
+            remark: format!("{:?}", std::time::SystemTime::now()).into_bytes(),
+        });
+        let call_hash = coordinator.propose(&proposer, &call).await?;
+
+        // nothing is old enough to be swept
+        assert_eq!(coordinator.sweep(&proposer, u32::MAX).await?.reclaimed(), 0);
+
+        let reclaimed = coordinator.cancel(&proposer, &call_hash).await?;
+        assert_eq!(reclaimed, deposits::multisig_deposit(&api, 2)?);
+        assert!(coordinator.status(&call_hash).await?.is_none());
+        assert!(coordinator.store().get(&call_hash)?.is_none());
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_sweep_stale_operations() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let store = CallStore::open(std::env::temp_dir().join("subxt-workshop-multisig"))?;
+        let other = sr25519::Pair::from_string("//MultisigSweep", None)
+            .map_err(|err| format!("{err:?}"))?
+            .public();
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let proposer = common::funded_account(api.clone(), &eve, "//MultisigSweepProposer", 1_000_000_000_000).await?;
+        let multisig = MultisigAccount::new(&api, [proposer.account_id().clone(), other.into()], 2)?;
+        let coordinator = MultisigCoordinator::new(api.clone(), multisig, store)?;
+        let remark = |label: &str| {
+            EncodedCall::System(SystemCall::remark {
+                remark: format!("{label} {:?}", std::time::SystemTime::now()).into_bytes(),
+            })
+        };
+
+        // the second proposal is finalized in a later block, so the first one is older than the head
+        let stale = coordinator.propose(&proposer, &remark("stale")).await?;
+        let recent = coordinator.propose(&proposer, &remark("recent")).await?;
+
+        let report = coordinator.sweep(&proposer, 0).await?;
+        let deposit = deposits::multisig_deposit(&api, 2)?;
+        let cancelled = report
+            .cancelled
+            .iter()
+            .find(|cancelled| cancelled.call_hash == stale)
+            .ok_or("Stale operation not cancelled!")?;
+        assert!(cancelled.height < report.block_number);
+        assert!(report.cancelled.iter().all(|cancelled| cancelled.reclaimed == deposit));
+        assert_eq!(report.reclaimed(), deposit * report.cancelled.len() as u128);
+        assert!(coordinator.status(&stale).await?.is_none());
+        assert!(coordinator.store().get(&stale)?.is_none());
+
+        // the recent operation is only swept if another block was finalized in between
+        if coordinator.status(&recent).await?.is_some() {
+            coordinator.cancel(&proposer, &recent).await?;
+        }
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,110 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::sp_core::crypto::Ss58Codec;
+use subxt_workshop::{
+    balance::Token,
+    payout_import::{parse_amount, parse_csv, parse_json, PayoutImporter, PayoutRow, RowIssue, SenderIssue},
+    with_default_client,
+};
+
+#[test]
+fn should_parse_payout_files() -> Result<(), Box<dyn Error>> {
+    let bob = AccountKeyring::Bob.to_account_id().to_ss58check();
+    let charlie = AccountKeyring::Charlie.to_account_id().to_ss58check();
+    let expected = vec![
+        PayoutRow {
+            line: 2,
+            address: bob.clone(),
+            amount: "1.5 DOT".to_string(),
+        },
+        PayoutRow {
+            line: 3,
+            address: charlie.clone(),
+            amount: "10000000000".to_string(),
+        },
+    ];
+
+    let csv = format!("name,address,amount\nbob, {bob}, 1.5 DOT\ncharlie,{charlie},10000000000\n");
+    assert_eq!(parse_csv(&csv)?, expected);
+
+    let json = format!(r#"[{{"address":"{bob}","amount":"1.5 DOT"}},{{"address":"{charlie}","amount":10000000000}}]"#);
+    let rows = parse_json(&json)?;
+    assert_eq!(
+        rows.iter().map(|row| (&row.address, &row.amount)).collect::<Vec<_>>(),
+        expected
+            .iter()
+            .map(|row| (&row.address, &row.amount))
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn should_parse_amounts() {
+    let dot = Token::new(10, "DOT");
+    assert_eq!(parse_amount("15", &dot), Ok(15));
+    assert_eq!(parse_amount("1.5", &dot), Ok(15_000_000_000));
+    assert_eq!(parse_amount("2 DOT", &dot), Ok(20_000_000_000));
+    assert!(parse_amount("1.5 KSM", &dot).is_err());
+    assert!(parse_amount("-1", &dot).is_err());
+}
+
+#[test]
+fn should_describe_issues_in_token() {
+    let token = Token::new(10, "DOT");
+    assert_eq!(
+        RowIssue::BelowExistentialDeposit {
+            balance_after: 5_000_000_000
+        }
+        .describe(&token),
+        "balance of 0.5000 DOT would be below the existential deposit"
+    );
+    assert_eq!(
+        SenderIssue::InsufficientBalance {
+            required: 25_000_000_000,
+            transferable: 10_000_000_000
+        }
+        .describe(&token),
+        "sender needs 2.5000 DOT but only 1.0000 DOT is transferable"
+    );
+}
+
+#[tokio::test]
+async fn should_preview_payout() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let rows = vec![
+            PayoutRow {
+                line: 1,
+                address: AccountKeyring::Bob.to_account_id().to_ss58check(),
+                amount: "1 DOT".to_string(),
+            },
+            // a fresh account cannot be created with less than the existential deposit
+            PayoutRow {
+                line: 2,
+                address: subxt::sp_runtime::AccountId32::new([42u8; 32]).to_ss58check(),
+                amount: "1".to_string(),
+            },
+            PayoutRow {
+                line: 3,
+                address: "not an address".to_string(),
+                amount: "1 DOT".to_string(),
+            },
+        ];
+
+        let preview = PayoutImporter::new(api.clone())
+            .preview(AccountKeyring::Alice.to_account_id(), rows)
+            .await?;
+        assert!(!preview.is_valid());
+        assert!(preview.sender_issues.is_empty());
+        assert!(preview.rows[0].issues.is_empty());
+        assert!(matches!(
+            preview.rows[1].issues[..],
+            [RowIssue::BelowExistentialDeposit { balance_after: 1 }]
+        ));
+        assert!(matches!(preview.rows[2].issues[..], [RowIssue::InvalidAddress(_)]));
+        assert_eq!(preview.recipients().len(), 1);
+        assert_eq!(preview.total, 10_000_000_000);
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,118 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::{
+    sp_runtime::{AccountId32, Perbill},
+    PairSigner,
+};
+use subxt_workshop::{fees::FeeEstimator, payout::PayoutEngine, polkadot, with_default_client, EncodedCall};
+
+type BalancesCall = polkadot::runtime_types::pallet_balances::pallet::Call;
+type UtilityCall = polkadot::runtime_types::pallet_utility::pallet::Call;
+
+#[tokio::test]
+async fn should_pay_out_in_chunks() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        // fresh accounts so other tests cannot interfere
+        let recipients: Vec<_> = (1..=5u8)
+            .map(|i| (AccountId32::new([i; 32]), 10_000_000_000 * i as u128))
+            .collect();
+        let engine = PayoutEngine::new(api.clone()).max_batch_size(2);
+
+        let chunks = engine
+            .plan(
+                AccountKeyring::Alice.to_account_id(),
+                recipients
+                    .iter()
+                    .map(|(account_id, amount)| (account_id.clone().into(), *amount))
+                    .collect(),
+            )
+            .await?;
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 2, 1],
+            "Chunks do not match!"
+        );
+
+        let mut balances_before = Vec::new();
+        for (account_id, _) in &recipients {
+            balances_before.push(api.storage().system().account(account_id, None).await?.data.free);
+        }
+
+        let report = engine
+            .pay(
+                &PairSigner::new(AccountKeyring::Alice.pair()),
+                recipients
+                    .iter()
+                    .map(|(account_id, amount)| (account_id.clone().into(), *amount))
+                    .collect(),
+            )
+            .await?;
+        assert_eq!(report.failed().count(), 0, "Payouts failed: {report:?}");
+        assert_eq!(
+            report.total_paid(),
+            recipients.iter().map(|(_, amount)| amount).sum::<u128>()
+        );
+
+        for ((account_id, amount), before) in recipients.iter().zip(balances_before) {
+            assert_eq!(
+                api.storage().system().account(account_id, None).await?.data.free,
+                before + amount,
+                "Balance was not sent to {account_id:?}!"
+            );
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fill_chunks_up_to_weight_limit() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let fraction = Perbill::from_perthousand(1);
+        let block_weights = api.constants().system().block_weights()?;
+        let normal = block_weights.per_class.normal;
+        let limit = fraction
+            * normal
+                .max_extrinsic
+                .or(normal.max_total)
+                .unwrap_or(block_weights.max_block);
+
+        let recipients: Vec<_> = (1..=24u8)
+            .map(|i| (AccountId32::new([i; 32]).into(), 10_000_000_000))
+            .collect();
+        let sender = AccountKeyring::Alice.to_account_id();
+        let chunks = PayoutEngine::new(api.clone())
+            .weight_fraction(fraction)
+            .plan(sender.clone(), recipients)
+            .await?;
+        assert!(chunks.len() > 1, "Weight limit did not split the payout!");
+
+        // every chunk fits, and all but the last would not fit another transfer
+        let estimator = FeeEstimator::new(api.clone());
+        let weight = |chunk: Vec<_>| {
+            let calls = chunk
+                .into_iter()
+                .map(|(dest, value)| EncodedCall::Balances(BalancesCall::transfer { dest, value }))
+                .collect();
+            let estimator = estimator.clone();
+            let sender = sender.clone();
+            async move {
+                let weight = estimator
+                    .estimate_for(&EncodedCall::Utility(UtilityCall::batch_all { calls }), sender)
+                    .await?
+                    .weight;
+                Ok::<_, Box<dyn Error>>(normal.base_extrinsic + weight)
+            }
+        };
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert!(weight(chunk.clone()).await? <= limit, "Chunk {i} is too heavy!");
+            if let Some(next) = chunks.get(i + 1) {
+                let mut fuller = chunk.clone();
+                fuller.push(next[0].clone());
+                assert!(weight(fuller).await? > limit, "Chunk {i} is not full!");
+            }
+        }
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,57 @@
+use sp_keyring::Ed25519Keyring;
+use std::{
+    error::Error,
+    io::{Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    thread,
+};
+use subxt::{extrinsic::Signer, sp_runtime::traits::Verify, PairSigner};
+use subxt_workshop::signer::{serve_remote_signer, Ed25519Signer, RemoteSigner};
+
+#[test]
+fn should_sign_with_remote_signer() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join(format!("subxt-workshop-signer-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+
+    let local: Ed25519Signer = PairSigner::new(Ed25519Keyring::Alice.pair());
+    let account_id = local.account_id().clone();
+    thread::spawn(move || serve_remote_signer(listener, local).unwrap());
+
+    let remote = RemoteSigner::connect(&path)?;
+    assert_eq!(remote.account_id(), &account_id, "Remote account does not match!");
+
+    let payload = b"signer payload";
+    assert!(
+        remote.sign(payload).verify(&payload[..], &account_id),
+        "Remote signature is invalid!"
+    );
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn should_reject_oversized_message() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join(format!("subxt-workshop-signer-{}-oversized.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+
+    let local: Ed25519Signer = PairSigner::new(Ed25519Keyring::Bob.pair());
+    let account_id = local.account_id().clone();
+    thread::spawn(move || serve_remote_signer(listener, local).unwrap());
+
+    // a length of 4 GiB ends the connection without allocating the message
+    let mut stream = UnixStream::connect(&path)?;
+    stream.write_all(&u32::MAX.to_le_bytes())?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    assert!(response.is_empty(), "Oversized message was answered!");
+
+    // the signing process keeps serving other connections
+    let remote = RemoteSigner::connect(&path)?;
+    assert_eq!(remote.account_id(), &account_id, "Remote account does not match!");
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use std::error::Error;
+use subxt::sp_runtime::AccountId32;
+use subxt_workshop::{
+    address::GENERIC_PREFIX,
+    stats::{get_distribution, gini, nearest_rank, Distribution},
+    with_default_client,
+};
+
+#[test]
+fn should_compute_distribution() {
+    assert_eq!(gini(&[5, 5, 5, 5]), 0.0);
+    assert!((gini(&[0, 0, 0, 100]) - 0.75).abs() < 1e-9);
+    assert_eq!(nearest_rank(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 50), 5);
+    assert_eq!(nearest_rank(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 99), 10);
+    assert_eq!(nearest_rank(&[], 50), 0);
+
+    let mut balances: Vec<_> = (1..=4u8).map(|i| (AccountId32::new([i; 32]), i as u128 * 10)).collect();
+    balances.push((AccountId32::new([0; 32]), 0));
+    let distribution = Distribution::new(balances, 2, GENERIC_PREFIX);
+    assert_eq!(distribution.holders, 4, "Empty accounts are not holders!");
+    assert_eq!(distribution.total, 100);
+    assert_eq!(
+        distribution
+            .top
+            .iter()
+            .map(|account| account.balance)
+            .collect::<Vec<_>>(),
+        vec![40, 30]
+    );
+}
+
+#[tokio::test]
+async fn should_get_distribution() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let report = get_distribution(api.clone(), None, 5).await?;
+        assert!(report.free.holders > 0);
+        assert!(report.free.holders <= report.accounts);
+        // the validator stashes are bonded on the dev chain
+        assert!(report.frozen.holders > 0);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()?)?;
+        assert_eq!(json["free"]["top"].as_array().map(Vec::len), Some(5));
+        assert!(json["free"]["total"].is_string(), "Balances should be strings!");
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::PairSigner;
+use subxt_workshop::{accounts, tips, with_default_client};
+
+#[tokio::test]
+async fn should_collect_tips() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let who = AccountKeyring::Eve.to_account_id();
+        let reason = format!("Reviewed PRs at {:?}", accounts::pin_block(&api, None).await?);
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let hash = tips::tip_new(api.clone(), &alice, &reason, who.clone(), 1_000_000_000).await?;
+
+        let overview = tips::get_tips(api.clone(), None).await?;
+        let tip = overview
+            .tips
+            .iter()
+            .find(|tip| tip.hash == hash)
+            .ok_or("Tip not found!")?;
+        assert_eq!(tip.reason, reason);
+        assert_eq!(tip.who, who);
+        assert_eq!(tip.median, 1_000_000_000);
+        assert_eq!(tip.payout(), tip.median);
+        assert_eq!(tip.closes, None);
+
+        // tip until the threshold is reached, which starts the countdown
+        let mut closing = false;
+        for (tipper, value) in common::COUNCIL
+            .iter()
+            .zip([2_000_000_000, 3_000_000_000, 4_000_000_000])
+        {
+            if tips::tip(api.clone(), &PairSigner::new(tipper.pair()), hash, value).await? {
+                closing = true;
+                break;
+            }
+        }
+        assert!(closing, "Tip did not reach the threshold!");
+
+        let overview = tips::get_tips(api.clone(), None).await?;
+        let tip = overview
+            .tips
+            .iter()
+            .find(|tip| tip.hash == hash)
+            .ok_or("Tip not found!")?;
+        assert_eq!(tip.tips.len(), overview.threshold);
+        let closes = tip.closes.ok_or("Tip is not closing!")?;
+        assert!(closes > overview.block_number);
+        assert!(closes <= overview.block_number + overview.countdown);
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::PairSigner;
+use subxt_workshop::{
+    balance::get_balance,
+    transfer::{transfer_all, transfer_keep_alive},
+    with_default_client,
+};
+
+#[tokio::test]
+async fn should_transfer_keep_alive() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let signer = common::funded_account(api.clone(), &eve, "//TransferKeepAlive", 100_000_000_000).await?;
+        let account = signer.account_id().clone();
+        let dest = AccountKeyring::Eve.to_account_id();
+        let transfer = transfer_keep_alive(api.clone(), &signer, dest.clone().into(), 10_000_000_000).await?;
+        assert_eq!(transfer.from, account);
+        assert_eq!(transfer.to, dest);
+        assert_eq!(transfer.amount.plancks, 10_000_000_000);
+
+        // sending everything would reap the account, so nothing is submitted
+        let free = get_balance(api.clone(), account).await?;
+        assert!(transfer_keep_alive(api.clone(), &signer, dest.into(), free.plancks)
+            .await
+            .is_err());
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_transfer_all_keep_alive() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let signer = common::funded_account(api.clone(), &eve, "//TransferAll", 100_000_000_000).await?;
+
+        let transfer = transfer_all(api.clone(), &signer, AccountKeyring::Eve.to_account_id().into(), true).await?;
+        assert!(transfer.amount.plancks > 0);
+        let existential_deposit = api.constants().balances().existential_deposit()?;
+        assert_eq!(
+            get_balance(api.clone(), transfer.from).await?.plancks,
+            existential_deposit
+        );
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,72 @@
+use sp_keyring::AccountKeyring;
+use std::error::Error;
+use subxt::{sp_runtime::Permill, PairSigner};
+use subxt_workshop::{
+    polkadot,
+    treasury::{get_treasury, predict_spend, TreasuryProposal},
+    tx, with_default_client, EncodedCall,
+};
+
+type TreasuryCall = polkadot::runtime_types::pallet_treasury::pallet::Call;
+type ProposedEvent = polkadot::treasury::events::Proposed;
+
+fn approved(index: u32, value: u128) -> TreasuryProposal {
+    TreasuryProposal {
+        index,
+        proposer: AccountKeyring::Alice.to_account_id(),
+        beneficiary: AccountKeyring::Bob.to_account_id(),
+        value,
+        bond: 0,
+        approved: true,
+    }
+}
+
+#[test]
+fn should_burn_remaining_pot() {
+    let proposals = [approved(0, 300), approved(1, 200)];
+    assert_eq!(
+        predict_spend(1_000, Permill::from_percent(10), &[0, 1], &proposals),
+        (500, 50)
+    );
+}
+
+#[test]
+fn should_not_burn_if_an_approval_is_missed() {
+    // the second proposal does not fit in the rest of the pot, the third one still does
+    let proposals = [approved(0, 600), approved(1, 500), approved(2, 100)];
+    assert_eq!(
+        predict_spend(1_000, Permill::from_percent(10), &[0, 1, 2], &proposals),
+        (700, 0)
+    );
+}
+
+#[tokio::test]
+async fn should_get_treasury() -> Result<(), Box<dyn Error>> {
+    with_default_client(|api| async move {
+        let call = EncodedCall::Treasury(TreasuryCall::propose_spend {
+            value: 10_000_000_000,
+            beneficiary: AccountKeyring::Bob.to_account_id().into(),
+        });
+        let events = tx::sign_and_submit(api.clone(), call, &PairSigner::new(AccountKeyring::Alice.pair())).await?;
+        let proposal_index = events
+            .find_first::<ProposedEvent>()?
+            .ok_or("Not proposed!")?
+            .proposal_index;
+
+        let treasury = get_treasury(api.clone(), Some(events.block_hash())).await?;
+        let proposal = treasury
+            .proposals
+            .iter()
+            .find(|proposal| proposal.index == proposal_index)
+            .ok_or("Proposal not found!")?;
+        assert_eq!(proposal.proposer, AccountKeyring::Alice.to_account_id());
+        assert_eq!(proposal.beneficiary, AccountKeyring::Bob.to_account_id());
+        assert!(!proposal.approved);
+
+        assert_eq!(treasury.next_spend % treasury.spend_period, 0);
+        assert!(treasury.next_spend > treasury.block_number);
+        assert!(treasury.expected_spend <= treasury.pot);
+        Ok(())
+    })
+    .await
+}